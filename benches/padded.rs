@@ -0,0 +1,44 @@
+//! Compares adjacent `Updatable` containers with and without cache-line
+//! padding when every thread hammers its own container.
+
+use anycow::AnyCow;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::hint::black_box;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const THREADS: usize = 4;
+
+fn hammer(cells: &[AnyCow<'static, u64>], iters: u64) -> Duration {
+    let start = Instant::now();
+    thread::scope(|s| {
+        for cell in cells {
+            s.spawn(move || {
+                for i in 0..iters {
+                    cell.try_replace(i).unwrap();
+                    black_box(*cell.borrow());
+                }
+            });
+        }
+    });
+    start.elapsed()
+}
+
+fn false_sharing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("adjacent_updatable");
+
+    let plain: Vec<_> = (0..THREADS).map(|_| AnyCow::updatable(0)).collect();
+    group.bench_function(BenchmarkId::new("plain", THREADS), |b| {
+        b.iter_custom(|iters| hammer(&plain, iters))
+    });
+
+    let padded: Vec<_> = (0..THREADS).map(|_| AnyCow::updatable_padded(0)).collect();
+    group.bench_function(BenchmarkId::new("padded", THREADS), |b| {
+        b.iter_custom(|iters| hammer(&padded, iters))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, false_sharing);
+criterion_main!(benches);
@@ -0,0 +1,35 @@
+//! Serial `iter().map()` versus `AnyCow::par_map` on a large vector.
+
+use anycow::AnyCow;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::hint::black_box;
+
+fn transform(x: &u64) -> u64 {
+    // Enough work per element for parallelism to matter
+    (0..32).fold(*x, |acc, i| acc.rotate_left(5) ^ i)
+}
+
+fn serial_vs_parallel(c: &mut Criterion) {
+    let mut group = c.benchmark_group("map_large_vec");
+
+    for len in [10_000usize, 1_000_000] {
+        let data: Vec<u64> = (0..len as u64).collect();
+
+        group.bench_function(BenchmarkId::new("serial", len), |b| {
+            let cow = AnyCow::borrowed(&data);
+            b.iter(|| {
+                let mapped: Vec<u64> = cow.borrow().iter().map(transform).collect();
+                black_box(mapped)
+            })
+        });
+
+        group.bench_function(BenchmarkId::new("par_map", len), |b| {
+            b.iter(|| black_box(AnyCow::borrowed(&data).par_map(transform)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, serial_vs_parallel);
+criterion_main!(benches);
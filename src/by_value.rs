@@ -0,0 +1,163 @@
+//! Stable-hashing wrapper around [`AnyCow`].
+
+use crate::AnyCow;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// An [`AnyCow`] whose value can't change, for use as a map key.
+///
+/// `AnyCow` hashes and compares the value it currently holds, so an
+/// `Updatable` or `Lazy` key whose value is replaced while it sits in a
+/// `HashMap` or `BTreeMap` silently corrupts the collection. `ByValue`
+/// rules that out: [`ByValue::new()`] turns an `Updatable` or `Lazy` into
+/// a `Shared` snapshot of its current value, while every other variant is
+/// kept as is. Taking the snapshot costs one atomic load and a reference
+/// count increment (plus initialization for an uninitialized `Lazy`); the
+/// payload itself is never cloned.
+///
+/// `Hash`, `Eq` and `Ord` delegate to the value, and `ByValue` dereferences
+/// to `T` without a guard. Because it holds no interior mutability,
+/// Clippy's `mutable_key_type` lint doesn't fire for it either.
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::{AnyCow, ByValue};
+/// use std::collections::HashSet;
+///
+/// let config = AnyCow::updatable(String::from("v1"));
+///
+/// let mut seen = HashSet::new();
+/// seen.insert(ByValue::new(config.clone()));
+///
+/// // Replacing the original doesn't affect the key
+/// config.try_replace(String::from("v2")).unwrap();
+/// assert!(seen.contains(&ByValue::new(AnyCow::owned(String::from("v1")))));
+/// ```
+pub struct ByValue<'a, T>(Frozen<'a, T>)
+where
+    T: 'a + ToOwned<Owned = T>;
+
+/// The variants of `AnyCow` that can't change their value.
+enum Frozen<'a, T> {
+    Borrowed(&'a T),
+    Owned(Box<T>),
+    Shared(Arc<T>),
+}
+
+impl<'a, T> ByValue<'a, T>
+where
+    T: 'a + ToOwned<Owned = T>,
+{
+    /// Wraps `cow`, replacing an `Updatable` or `Lazy` with a `Shared`
+    /// snapshot of its current value.
+    pub fn new(cow: AnyCow<'a, T>) -> Self {
+        ByValue(match cow {
+            AnyCow::Borrowed(value) => Frozen::Borrowed(value),
+            AnyCow::Owned(value) => Frozen::Owned(value),
+            AnyCow::Shared(value) => Frozen::Shared(value),
+            guarded => Frozen::Shared(guarded.to_arc()),
+        })
+    }
+
+    /// Returns a reference to the value.
+    pub fn get(&self) -> &T {
+        self
+    }
+
+    /// Unwraps the frozen `AnyCow`.
+    ///
+    /// The result is never an `Updatable` or `Lazy` variant.
+    pub fn into_inner(self) -> AnyCow<'a, T> {
+        match self.0 {
+            Frozen::Borrowed(value) => AnyCow::Borrowed(value),
+            Frozen::Owned(value) => AnyCow::Owned(value),
+            Frozen::Shared(value) => AnyCow::Shared(value),
+        }
+    }
+}
+
+impl<'a, T> Deref for ByValue<'a, T>
+where
+    T: 'a + ToOwned<Owned = T>,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match &self.0 {
+            Frozen::Borrowed(value) => value,
+            Frozen::Owned(value) => value,
+            Frozen::Shared(value) => value,
+        }
+    }
+}
+
+impl<'a, T> From<AnyCow<'a, T>> for ByValue<'a, T>
+where
+    T: 'a + ToOwned<Owned = T>,
+{
+    fn from(cow: AnyCow<'a, T>) -> Self {
+        ByValue::new(cow)
+    }
+}
+
+impl<'a, T> Clone for ByValue<'a, T>
+where
+    T: 'a + ToOwned<Owned = T> + Clone,
+{
+    fn clone(&self) -> Self {
+        ByValue(match &self.0 {
+            Frozen::Borrowed(value) => Frozen::Borrowed(value),
+            Frozen::Owned(value) => Frozen::Owned(value.clone()),
+            Frozen::Shared(value) => Frozen::Shared(value.clone()),
+        })
+    }
+}
+
+impl<'a, T> std::fmt::Debug for ByValue<'a, T>
+where
+    T: 'a + ToOwned<Owned = T> + std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ByValue").field(self.get()).finish()
+    }
+}
+
+impl<'a, T> PartialEq for ByValue<'a, T>
+where
+    T: 'a + ToOwned<Owned = T> + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.get() == other.get()
+    }
+}
+
+impl<'a, T> Eq for ByValue<'a, T> where T: 'a + ToOwned<Owned = T> + Eq {}
+
+impl<'a, T> Hash for ByValue<'a, T>
+where
+    T: 'a + ToOwned<Owned = T> + Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.get().hash(state)
+    }
+}
+
+impl<'a, T> PartialOrd for ByValue<'a, T>
+where
+    T: 'a + ToOwned<Owned = T> + PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.get().partial_cmp(other.get())
+    }
+}
+
+impl<'a, T> Ord for ByValue<'a, T>
+where
+    T: 'a + ToOwned<Owned = T> + Ord,
+{
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.get().cmp(other.get())
+    }
+}
@@ -0,0 +1,284 @@
+//! Atomic element-level updates for `Updatable` collections.
+
+use crate::{AnyCow, AnyCowReplaceError};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::hash::{BuildHasher, Hash};
+
+/// Element-level updates for `Updatable` and `Lazy` collections.
+///
+/// Each method clones the current collection, applies one change and
+/// stores the result atomically, retrying if another writer got there
+/// first, so readers see either the old or the new collection, never a
+/// partial change. Every update copies the whole collection; this suits
+/// small, rarely written collections such as configuration maps.
+///
+/// Bulk filtering and resetting don't need this trait: the inherent
+/// [`AnyCow::retain()`] and [`AnyCow::clear()`] already cover every
+/// collection.
+///
+/// All methods return `Err(AnyCowReplaceError)` if the container is not an
+/// `Updatable` or `Lazy` variant, or if the validator of an
+/// [`AnyCow::updatable_validated()`] container refuses the result; a `Lazy`
+/// is initialized first.
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::{AnyCow, UpdatableCollectionExt};
+/// use std::collections::HashMap;
+///
+/// let routes = AnyCow::updatable(HashMap::new());
+/// routes.insert(("/health", 1)).unwrap();
+/// routes.insert(("/metrics", 2)).unwrap();
+/// routes.remove(&"/health").unwrap();
+///
+/// assert_eq!(routes.borrow().len(), 1);
+/// assert_eq!(routes.borrow()["/metrics"], 2);
+/// ```
+pub trait UpdatableCollectionExt {
+    /// The element added by [`insert()`](Self::insert): the element type for
+    /// sequences and sets, a `(key, value)` pair for maps.
+    type Item;
+
+    /// What [`remove()`](Self::remove) looks elements up by: an index for
+    /// sequences, the element for sets, the key for maps.
+    type Key;
+
+    /// Atomically adds `item`.
+    ///
+    /// Sequences append it at the back, sets and maps insert it, replacing
+    /// an equal element or the value of an existing key.
+    fn insert(&self, item: Self::Item) -> Result<(), AnyCowReplaceError>;
+
+    /// Atomically removes the element at `key`.
+    ///
+    /// Removing a key that isn't present, or an index that is out of
+    /// bounds, leaves the collection unchanged.
+    fn remove(&self, key: &Self::Key) -> Result<(), AnyCowReplaceError>;
+
+    /// Atomically adds every item of `items`, as one update.
+    ///
+    /// The collection is cloned once and all items are added to the copy
+    /// before it is stored, so readers see either none of them or all of
+    /// them. Prefer this over calling [`insert()`](Self::insert) in a loop,
+    /// which clones the collection and publishes an intermediate state
+    /// for every item.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::{AnyCow, UpdatableCollectionExt};
+    /// use std::collections::HashMap;
+    ///
+    /// let limits = AnyCow::updatable(HashMap::from([("GET", 100)]));
+    /// limits.insert_many([("GET", 200), ("POST", 20)]).unwrap();
+    ///
+    /// assert_eq!(limits.borrow()["GET"], 200);
+    /// assert_eq!(limits.borrow()["POST"], 20);
+    /// ```
+    fn insert_many<I>(&self, items: I) -> Result<(), AnyCowReplaceError>
+    where
+        I: IntoIterator<Item = Self::Item>;
+
+    /// Atomically appends `item`; the sequence spelling of
+    /// [`insert()`](Self::insert).
+    fn push(&self, item: Self::Item) -> Result<(), AnyCowReplaceError> {
+        self.insert(item)
+    }
+}
+
+/// Clones the current collection, applies `f` and stores the result.
+fn update<C, F>(cow: &AnyCow<'_, C>, mut f: F) -> Result<(), AnyCowReplaceError>
+where
+    C: Clone,
+    F: FnMut(&mut C),
+{
+    let handle = cow.as_updatable().ok_or(AnyCowReplaceError)?;
+    handle
+        .rcu(|current| {
+            let mut value = current.clone();
+            f(&mut value);
+            value
+        })
+        .map(drop)
+        .map_err(|_| AnyCowReplaceError)
+}
+
+/// Adds all `items` in a single update.
+fn extend<C, I>(cow: &AnyCow<'_, C>, items: I) -> Result<(), AnyCowReplaceError>
+where
+    C: Clone + Extend<I::Item>,
+    I: IntoIterator,
+    I::Item: Clone,
+{
+    // Collected up front, since the update may have to be retried.
+    let items: Vec<_> = items.into_iter().collect();
+    if items.is_empty() {
+        return cow.as_updatable().map(|_| ()).ok_or(AnyCowReplaceError);
+    }
+    update(cow, |collection| collection.extend(items.iter().cloned()))
+}
+
+impl<T> UpdatableCollectionExt for AnyCow<'_, Vec<T>>
+where
+    T: Clone,
+{
+    type Item = T;
+    type Key = usize;
+
+    fn insert(&self, item: T) -> Result<(), AnyCowReplaceError> {
+        update(self, |items| items.push(item.clone()))
+    }
+
+    fn remove(&self, index: &usize) -> Result<(), AnyCowReplaceError> {
+        update(self, |items| {
+            if *index < items.len() {
+                items.remove(*index);
+            }
+        })
+    }
+
+    fn insert_many<I>(&self, items: I) -> Result<(), AnyCowReplaceError>
+    where
+        I: IntoIterator<Item = Self::Item>,
+    {
+        extend(self, items)
+    }
+}
+
+impl<T> UpdatableCollectionExt for AnyCow<'_, VecDeque<T>>
+where
+    T: Clone,
+{
+    type Item = T;
+    type Key = usize;
+
+    fn insert(&self, item: T) -> Result<(), AnyCowReplaceError> {
+        update(self, |items| items.push_back(item.clone()))
+    }
+
+    fn remove(&self, index: &usize) -> Result<(), AnyCowReplaceError> {
+        update(self, |items| {
+            items.remove(*index);
+        })
+    }
+
+    fn insert_many<I>(&self, items: I) -> Result<(), AnyCowReplaceError>
+    where
+        I: IntoIterator<Item = Self::Item>,
+    {
+        extend(self, items)
+    }
+}
+
+impl<T, S> UpdatableCollectionExt for AnyCow<'_, HashSet<T, S>>
+where
+    T: Clone + Eq + Hash,
+    S: Clone + BuildHasher,
+{
+    type Item = T;
+    type Key = T;
+
+    fn insert(&self, item: T) -> Result<(), AnyCowReplaceError> {
+        update(self, |items| {
+            items.insert(item.clone());
+        })
+    }
+
+    fn remove(&self, item: &T) -> Result<(), AnyCowReplaceError> {
+        update(self, |items| {
+            items.remove(item);
+        })
+    }
+
+    fn insert_many<I>(&self, items: I) -> Result<(), AnyCowReplaceError>
+    where
+        I: IntoIterator<Item = Self::Item>,
+    {
+        extend(self, items)
+    }
+}
+
+impl<T> UpdatableCollectionExt for AnyCow<'_, BTreeSet<T>>
+where
+    T: Clone + Ord,
+{
+    type Item = T;
+    type Key = T;
+
+    fn insert(&self, item: T) -> Result<(), AnyCowReplaceError> {
+        update(self, |items| {
+            items.insert(item.clone());
+        })
+    }
+
+    fn remove(&self, item: &T) -> Result<(), AnyCowReplaceError> {
+        update(self, |items| {
+            items.remove(item);
+        })
+    }
+
+    fn insert_many<I>(&self, items: I) -> Result<(), AnyCowReplaceError>
+    where
+        I: IntoIterator<Item = Self::Item>,
+    {
+        extend(self, items)
+    }
+}
+
+impl<K, V, S> UpdatableCollectionExt for AnyCow<'_, HashMap<K, V, S>>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+    S: Clone + BuildHasher,
+{
+    type Item = (K, V);
+    type Key = K;
+
+    fn insert(&self, (key, value): (K, V)) -> Result<(), AnyCowReplaceError> {
+        update(self, |map| {
+            map.insert(key.clone(), value.clone());
+        })
+    }
+
+    fn remove(&self, key: &K) -> Result<(), AnyCowReplaceError> {
+        update(self, |map| {
+            map.remove(key);
+        })
+    }
+
+    fn insert_many<I>(&self, items: I) -> Result<(), AnyCowReplaceError>
+    where
+        I: IntoIterator<Item = Self::Item>,
+    {
+        extend(self, items)
+    }
+}
+
+impl<K, V> UpdatableCollectionExt for AnyCow<'_, BTreeMap<K, V>>
+where
+    K: Clone + Ord,
+    V: Clone,
+{
+    type Item = (K, V);
+    type Key = K;
+
+    fn insert(&self, (key, value): (K, V)) -> Result<(), AnyCowReplaceError> {
+        update(self, |map| {
+            map.insert(key.clone(), value.clone());
+        })
+    }
+
+    fn remove(&self, key: &K) -> Result<(), AnyCowReplaceError> {
+        update(self, |map| {
+            map.remove(key);
+        })
+    }
+
+    fn insert_many<I>(&self, items: I) -> Result<(), AnyCowReplaceError>
+    where
+        I: IntoIterator<Item = Self::Item>,
+    {
+        extend(self, items)
+    }
+}
@@ -0,0 +1,333 @@
+//! Traits describing collection payloads, used by the collection-aware
+//! helpers on [`AnyCow`](crate::AnyCow).
+
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::{BuildHasher, Hash};
+
+/// Collections that can release unused capacity.
+///
+/// Used by [`AnyCow::compact()`](crate::AnyCow::compact) to shrink
+/// `Updatable` payloads that have grown and then shrunk over time.
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::Compact;
+///
+/// let mut v = Vec::with_capacity(16);
+/// v.push(1);
+/// v.compact();
+/// assert_eq!(v.capacity(), 1);
+/// ```
+pub trait Compact {
+    /// Shrinks the capacity of the collection as much as possible.
+    fn compact(&mut self);
+}
+
+impl<T> Compact for Vec<T> {
+    fn compact(&mut self) {
+        self.shrink_to_fit();
+    }
+}
+
+impl<T> Compact for VecDeque<T> {
+    fn compact(&mut self) {
+        self.shrink_to_fit();
+    }
+}
+
+impl<T: Ord> Compact for BinaryHeap<T> {
+    fn compact(&mut self) {
+        self.shrink_to_fit();
+    }
+}
+
+impl Compact for String {
+    fn compact(&mut self) {
+        self.shrink_to_fit();
+    }
+}
+
+impl<K, V, S> Compact for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    fn compact(&mut self) {
+        self.shrink_to_fit();
+    }
+}
+
+impl<T, S> Compact for HashSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    fn compact(&mut self) {
+        self.shrink_to_fit();
+    }
+}
+
+/// Collections that can be created with preallocated capacity.
+///
+/// Used by [`AnyCow::updatable_with_capacity()`](crate::AnyCow::updatable_with_capacity).
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::WithCapacity;
+///
+/// let v: Vec<u8> = WithCapacity::with_capacity(64);
+/// assert!(v.capacity() >= 64);
+/// ```
+pub trait WithCapacity {
+    /// Creates an empty collection with room for at least `capacity`
+    /// elements.
+    fn with_capacity(capacity: usize) -> Self;
+}
+
+impl<T> WithCapacity for Vec<T> {
+    fn with_capacity(capacity: usize) -> Self {
+        Vec::with_capacity(capacity)
+    }
+}
+
+impl<T> WithCapacity for VecDeque<T> {
+    fn with_capacity(capacity: usize) -> Self {
+        VecDeque::with_capacity(capacity)
+    }
+}
+
+impl<T: Ord> WithCapacity for BinaryHeap<T> {
+    fn with_capacity(capacity: usize) -> Self {
+        BinaryHeap::with_capacity(capacity)
+    }
+}
+
+impl WithCapacity for String {
+    fn with_capacity(capacity: usize) -> Self {
+        String::with_capacity(capacity)
+    }
+}
+
+impl<K, V, S> WithCapacity for HashMap<K, V, S>
+where
+    S: BuildHasher + Default,
+{
+    fn with_capacity(capacity: usize) -> Self {
+        HashMap::with_capacity_and_hasher(capacity, S::default())
+    }
+}
+
+impl<T, S> WithCapacity for HashSet<T, S>
+where
+    S: BuildHasher + Default,
+{
+    fn with_capacity(capacity: usize) -> Self {
+        HashSet::with_capacity_and_hasher(capacity, S::default())
+    }
+}
+
+/// Collections that can drop elements matching a predicate in place.
+///
+/// Used by [`AnyCow::retain()`](crate::AnyCow::retain) to filter
+/// `Updatable` collections atomically. The predicate `F` has the same shape
+/// as the collection's own `retain`: sequences and sets take `FnMut(&T)`,
+/// maps take `FnMut(&K, &V)` and `String` takes `FnMut(char)`.
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::Retainable;
+/// use std::collections::HashMap;
+///
+/// let mut map = HashMap::from([(1, "a"), (2, "b")]);
+/// map.retain_items(&mut |key: &i32, _: &&str| *key > 1);
+/// assert_eq!(map.len(), 1);
+/// ```
+pub trait Retainable<F> {
+    /// Keeps only the elements for which `f` returns `true`.
+    fn retain_items(&mut self, f: &mut F);
+}
+
+impl<T, F> Retainable<F> for Vec<T>
+where
+    F: FnMut(&T) -> bool,
+{
+    fn retain_items(&mut self, f: &mut F) {
+        self.retain(f);
+    }
+}
+
+impl<T, F> Retainable<F> for VecDeque<T>
+where
+    F: FnMut(&T) -> bool,
+{
+    fn retain_items(&mut self, f: &mut F) {
+        self.retain(f);
+    }
+}
+
+impl<T, F> Retainable<F> for BinaryHeap<T>
+where
+    T: Ord,
+    F: FnMut(&T) -> bool,
+{
+    fn retain_items(&mut self, f: &mut F) {
+        self.retain(f);
+    }
+}
+
+impl<F> Retainable<F> for String
+where
+    F: FnMut(char) -> bool,
+{
+    fn retain_items(&mut self, f: &mut F) {
+        self.retain(f);
+    }
+}
+
+impl<T, S, F> Retainable<F> for HashSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    F: FnMut(&T) -> bool,
+{
+    fn retain_items(&mut self, f: &mut F) {
+        self.retain(f);
+    }
+}
+
+impl<T, F> Retainable<F> for BTreeSet<T>
+where
+    T: Ord,
+    F: FnMut(&T) -> bool,
+{
+    fn retain_items(&mut self, f: &mut F) {
+        self.retain(f);
+    }
+}
+
+impl<K, V, S, F> Retainable<F> for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    F: FnMut(&K, &V) -> bool,
+{
+    fn retain_items(&mut self, f: &mut F) {
+        self.retain(|key, value| f(key, value));
+    }
+}
+
+impl<K, V, F> Retainable<F> for BTreeMap<K, V>
+where
+    K: Ord,
+    F: FnMut(&K, &V) -> bool,
+{
+    fn retain_items(&mut self, f: &mut F) {
+        self.retain(|key, value| f(key, value));
+    }
+}
+
+/// Payloads that can be "empty", used by [`AnyCow::or()`](crate::AnyCow::or)
+/// and [`AnyCow::or_else()`](crate::AnyCow::or_else) to decide whether to
+/// fall back.
+///
+/// The default implementation reports a value as never empty, so an empty
+/// `impl` opts a type in without ever falling back. `Option` is empty when
+/// `None`, and the standard collections and `String` when they contain no
+/// elements.
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::MaybeEmpty;
+///
+/// struct Config {
+///     url: String,
+/// }
+///
+/// // Empty when no URL is configured
+/// impl MaybeEmpty for Config {
+///     fn is_empty_value(&self) -> bool {
+///         self.url.is_empty()
+///     }
+/// }
+///
+/// assert!(None::<i32>.is_empty_value());
+/// assert!(!vec![1].is_empty_value());
+/// assert!(Config { url: String::new() }.is_empty_value());
+/// ```
+pub trait MaybeEmpty {
+    /// Returns `true` if the value should be replaced by a fallback.
+    fn is_empty_value(&self) -> bool {
+        false
+    }
+}
+
+impl<T> MaybeEmpty for Option<T> {
+    fn is_empty_value(&self) -> bool {
+        self.is_none()
+    }
+}
+
+impl MaybeEmpty for String {
+    fn is_empty_value(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+macro_rules! impl_maybe_empty_for_collections {
+    ($($ty:ident<$($param:ident),*>),*) => {
+        $(
+            impl<$($param),*> MaybeEmpty for $ty<$($param),*> {
+                fn is_empty_value(&self) -> bool {
+                    self.is_empty()
+                }
+            }
+        )*
+    };
+}
+
+impl_maybe_empty_for_collections!(
+    Vec<T>,
+    VecDeque<T>,
+    BinaryHeap<T>,
+    HashMap<K, V, S>,
+    HashSet<T, S>,
+    BTreeMap<K, V>,
+    BTreeSet<T>
+);
+
+/// Types with an empty value that lives for `'static`.
+///
+/// Backs [`AnyCow::empty()`](crate::AnyCow::empty), which borrows
+/// [`EMPTY`](Self::EMPTY) instead of allocating an empty collection.
+pub trait StaticEmpty: 'static {
+    /// A reference to the empty value.
+    const EMPTY: &'static Self;
+}
+
+impl<T: 'static> StaticEmpty for Vec<T> {
+    const EMPTY: &'static Self = &Vec::new();
+}
+
+impl StaticEmpty for String {
+    const EMPTY: &'static Self = &String::new();
+}
+
+impl<T: 'static> StaticEmpty for VecDeque<T> {
+    const EMPTY: &'static Self = &VecDeque::new();
+}
+
+impl<T: 'static> StaticEmpty for BTreeSet<T> {
+    const EMPTY: &'static Self = &BTreeSet::new();
+}
+
+impl<K: 'static, V: 'static> StaticEmpty for BTreeMap<K, V> {
+    const EMPTY: &'static Self = &BTreeMap::new();
+}
+
+impl<T: 'static> StaticEmpty for Option<T> {
+    const EMPTY: &'static Self = &None;
+}
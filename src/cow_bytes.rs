@@ -0,0 +1,167 @@
+//! String encodings for byte payloads, enabled by the `serde` feature.
+//!
+//! By default `serde` writes a `Vec<u8>` as a sequence of numbers, which in
+//! JSON is several times larger than the data and unreadable. The
+//! [`as_base64`] and [`as_hex`] modules serialize an `AnyCow<Vec<u8>>` as a
+//! string instead, for use with `#[serde(with = "...")]`. Both deserialize
+//! into the `Owned` variant.
+
+use crate::AnyCow;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serializer};
+
+/// Serializes an `AnyCow<Vec<u8>>` as a padded, standard alphabet base64
+/// string (RFC 4648).
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::AnyCow;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Key {
+///     #[serde(with = "anycow::as_base64")]
+///     secret: AnyCow<'static, Vec<u8>>,
+/// }
+///
+/// let key = Key { secret: AnyCow::owned(b"foobar".to_vec()) };
+/// let json = serde_json::to_string(&key).unwrap();
+/// assert_eq!(json, r#"{"secret":"Zm9vYmFy"}"#);
+///
+/// let key: Key = serde_json::from_str(&json).unwrap();
+/// assert_eq!(*key.secret.borrow(), b"foobar");
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod as_base64 {
+    use super::*;
+
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    /// Serializes the bytes as a base64 string.
+    pub fn serialize<S>(cow: &AnyCow<'_, Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&encode(&cow.borrow()))
+    }
+
+    /// Decodes a base64 string into an `Owned` `AnyCow`.
+    pub fn deserialize<'de, 'a, D>(deserializer: D) -> Result<AnyCow<'a, Vec<u8>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let text = String::deserialize(deserializer)?;
+        decode(&text).map(AnyCow::owned).map_err(D::Error::custom)
+    }
+
+    fn encode(bytes: &[u8]) -> String {
+        let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+        for chunk in bytes.chunks(3) {
+            let b = [
+                chunk[0],
+                *chunk.get(1).unwrap_or(&0),
+                *chunk.get(2).unwrap_or(&0),
+            ];
+            let n = u32::from(b[0]) << 16 | u32::from(b[1]) << 8 | u32::from(b[2]);
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+                } else {
+                    out.push('=');
+                }
+            }
+        }
+        out
+    }
+
+    fn decode(text: &str) -> Result<Vec<u8>, &'static str> {
+        let text = text.as_bytes();
+        if !text.len().is_multiple_of(4) {
+            return Err("base64 length must be a multiple of 4");
+        }
+        let mut out = Vec::with_capacity(text.len() / 4 * 3);
+        for (index, chunk) in text.chunks(4).enumerate() {
+            let is_last = index == text.len() / 4 - 1;
+            let padding = chunk.iter().rev().take_while(|&&c| c == b'=').count();
+            if padding > 2 || (padding > 0 && !is_last) {
+                return Err("invalid base64 padding");
+            }
+            let mut n = 0u32;
+            for &c in &chunk[..4 - padding] {
+                let digit = ALPHABET
+                    .iter()
+                    .position(|&a| a == c)
+                    .ok_or("invalid base64 character")?;
+                n = n << 6 | digit as u32;
+            }
+            n <<= 6 * padding;
+            out.extend_from_slice(&n.to_be_bytes()[1..4 - padding]);
+        }
+        Ok(out)
+    }
+}
+
+/// Serializes an `AnyCow<Vec<u8>>` as a lower case hex string.
+///
+/// Deserialization accepts upper and lower case digits.
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::AnyCow;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Blob {
+///     #[serde(with = "anycow::as_hex")]
+///     digest: AnyCow<'static, Vec<u8>>,
+/// }
+///
+/// let blob: Blob = serde_json::from_str(r#"{"digest":"DEADbeef"}"#).unwrap();
+/// assert_eq!(*blob.digest.borrow(), [0xde, 0xad, 0xbe, 0xef]);
+/// assert_eq!(serde_json::to_string(&blob).unwrap(), r#"{"digest":"deadbeef"}"#);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod as_hex {
+    use super::*;
+
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    /// Serializes the bytes as a hex string.
+    pub fn serialize<S>(cow: &AnyCow<'_, Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let bytes = cow.borrow();
+        let mut out = String::with_capacity(bytes.len() * 2);
+        for &byte in bytes.iter() {
+            out.push(DIGITS[usize::from(byte >> 4)] as char);
+            out.push(DIGITS[usize::from(byte & 0xf)] as char);
+        }
+        serializer.serialize_str(&out)
+    }
+
+    /// Decodes a hex string into an `Owned` `AnyCow`.
+    pub fn deserialize<'de, 'a, D>(deserializer: D) -> Result<AnyCow<'a, Vec<u8>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let text = String::deserialize(deserializer)?;
+        if !text.len().is_multiple_of(2) {
+            return Err(D::Error::custom("hex string has an odd length"));
+        }
+        text.as_bytes()
+            .chunks(2)
+            .map(|pair| {
+                let high = (pair[0] as char).to_digit(16);
+                let low = (pair[1] as char).to_digit(16);
+                match (high, low) {
+                    (Some(high), Some(low)) => Ok((high << 4 | low) as u8),
+                    _ => Err(D::Error::custom("invalid hex digit")),
+                }
+            })
+            .collect::<Result<Vec<u8>, _>>()
+            .map(AnyCow::owned)
+    }
+}
@@ -0,0 +1,109 @@
+//! `Cow<str>`-style string helpers for `AnyCow<String>`.
+//!
+//! `AnyCow` can't hold an unsized `str`, so these helpers live on
+//! `AnyCow<String>` and hand out `str` views through [`MappedRef`]. Each one
+//! allocates only when the result actually differs from the input.
+
+use crate::{AnyCow, AnyCowRef, MappedRef};
+
+impl<'a> AnyCow<'a, String> {
+    /// Converts the string to ASCII upper case in place.
+    ///
+    /// Like [`to_mut()`](Self::to_mut), this clones non-`Owned` data into
+    /// an `Owned` string first, but only if the string contains a lower
+    /// case ASCII letter; an already upper case string is left untouched,
+    /// whichever variant holds it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let header = String::from("CONTENT-TYPE");
+    /// let mut cow = AnyCow::borrowed(&header);
+    /// cow.make_ascii_uppercase();
+    /// assert!(cow.is_borrowed());
+    ///
+    /// let method = String::from("get");
+    /// let mut cow = AnyCow::borrowed(&method);
+    /// cow.make_ascii_uppercase();
+    /// assert!(cow.is_owned());
+    /// assert_eq!(*cow.borrow(), "GET");
+    /// ```
+    pub fn make_ascii_uppercase(&mut self) {
+        if self.borrow().bytes().any(|b| b.is_ascii_lowercase()) {
+            self.to_mut().make_ascii_uppercase();
+        }
+    }
+
+    /// Returns the string without leading and trailing whitespace.
+    ///
+    /// The result borrows from the existing data, so nothing is copied;
+    /// for `Updatable` and `Lazy` variants the [`MappedRef`] keeps the
+    /// guard alive.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let line = AnyCow::updatable(String::from("  key = value\n"));
+    /// assert_eq!(&*line.trim(), "key = value");
+    /// ```
+    pub fn trim(&self) -> MappedRef<'_, String, str> {
+        AnyCowRef::map(self.borrow(), |s| s.trim())
+    }
+
+    /// Returns the lower case equivalent of the string.
+    ///
+    /// A string that is already lower case isn't copied: a `Borrowed`
+    /// stays borrowed, and `Shared`, `Updatable` and `Lazy` variants return
+    /// a `Shared` reference to their current value. Only an `Owned` string
+    /// has to be cloned, since the result can't borrow from `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let name = String::from("Straße");
+    /// let lower = AnyCow::borrowed(&name).to_lowercase();
+    /// assert!(lower.is_owned());
+    /// assert_eq!(*lower.borrow(), "straße");
+    ///
+    /// let host = String::from("example.org");
+    /// let already = AnyCow::borrowed(&host).to_lowercase();
+    /// assert!(already.is_borrowed());
+    /// ```
+    pub fn to_lowercase(&self) -> AnyCow<'a, String> {
+        match self {
+            AnyCow::Borrowed(value) => match lowercased(value) {
+                Some(lower) => AnyCow::Owned(Box::new(lower)),
+                None => AnyCow::Borrowed(*value),
+            },
+            AnyCow::Owned(value) => AnyCow::Owned(Box::new(
+                lowercased(value).unwrap_or_else(|| (**value).clone()),
+            )),
+            AnyCow::Shared(_) | AnyCow::Updatable(_) | AnyCow::Tracked(_) | AnyCow::Lazy { .. } => {
+                // Check and return the same snapshot, even if the value is
+                // replaced meanwhile.
+                let current = self.to_arc();
+                match lowercased(&current) {
+                    Some(lower) => AnyCow::Owned(Box::new(lower)),
+                    None => AnyCow::Shared(current),
+                }
+            }
+        }
+    }
+}
+
+/// Returns the lower case form of `s`, or `None` if that's `s` itself.
+fn lowercased(s: &str) -> Option<String> {
+    // Per-character comparison is enough: the only context-dependent
+    // mapping, the final sigma, starts from an upper case letter.
+    if s.chars().flat_map(char::to_lowercase).eq(s.chars()) {
+        None
+    } else {
+        Some(s.to_lowercase())
+    }
+}
@@ -0,0 +1,64 @@
+//! Heap usage estimation for payloads, used by
+//! [`AnyCow::heap_size()`](crate::AnyCow::heap_size).
+
+/// Payloads that can report how much heap memory they own.
+///
+/// Implement this for your own payload types to budget caches of `AnyCow`
+/// values. The reported size excludes `size_of::<Self>()` itself, which
+/// `AnyCow::heap_size()` adds when the value lives in a `Box` or `Arc`.
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::HeapSize;
+///
+/// struct Entry {
+///     key: String,
+///     payload: Vec<u8>,
+/// }
+///
+/// impl HeapSize for Entry {
+///     fn heap_size(&self) -> usize {
+///         self.key.heap_size() + self.payload.heap_size()
+///     }
+/// }
+///
+/// let entry = Entry {
+///     key: String::with_capacity(8),
+///     payload: Vec::with_capacity(100),
+/// };
+/// assert_eq!(entry.heap_size(), 108);
+/// ```
+pub trait HeapSize {
+    /// Returns the number of heap bytes owned by this value.
+    fn heap_size(&self) -> usize;
+}
+
+impl<T: HeapSize> HeapSize for Vec<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * std::mem::size_of::<T>()
+            + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+impl HeapSize for String {
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+macro_rules! impl_heap_size_for_scalars {
+    ($($ty:ty),*) => {
+        $(
+            impl HeapSize for $ty {
+                fn heap_size(&self) -> usize {
+                    0
+                }
+            }
+        )*
+    };
+}
+
+impl_heap_size_for_scalars!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, char
+);
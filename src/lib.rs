@@ -18,8 +18,36 @@
 //! - [`AnyCow::Owned`] - Heap-allocated owned data via `Box<T>`
 //! - [`AnyCow::Shared`] - `Arc<T>` for shared immutable data across threads
 //! - [`AnyCow::Updatable`] - Lock-free atomic updates using `arc-swap`
+//! - [`AnyCow::Tracked`] - `Updatable` plus write counting, history and write policies
 //! - [`AnyCow::Lazy`] - Lazy initialization with atomic updates for static contexts
 //!
+//! Single-threaded code that doesn't need atomics can use [`LocalAnyCow`],
+//! which shares data through `Rc<T>` instead. Trait objects and other
+//! unsized types can be shared and hot-swapped with [`SharedAnyCow`].
+//!
+//! ## Const Construction
+//!
+//! The following are `const fn` and can be used in `const` and `static` items:
+//!
+//! - [`AnyCow::borrowed`] - for references to data that outlives the item
+//! - [`AnyCow::empty`] - a borrowed empty `Vec`, `String` or other [`StaticEmpty`] value
+//! - [`AnyCow::shared`] - callable from `const fn`, though building the `Arc`
+//!   itself isn't possible in a `const` context
+//! - [`AnyCow::lazy`] - the deferred form of [`AnyCow::owned`] and
+//!   [`AnyCow::updatable`], which can't be `const` because `Box::new` and
+//!   `Arc::new` aren't
+//! - the `is_*` predicates, such as [`AnyCow::is_lazy`]
+//!
+//! ## Optional Features
+//!
+//! - `crossbeam` - `AnyCow::updatable_channel`, which sends every
+//!   replacement down a `crossbeam-channel` receiver
+//! - `rayon` - parallel transforms such as `AnyCow::par_map`
+//! - `serde` - `Serialize`/`Deserialize` for `AnyCow`, plus the
+//!   `as_updatable` and `as_shared` modules for choosing the variant a
+//!   field deserializes into, and `as_base64`/`as_hex` for byte payloads
+//! - `tokio` - `AnyCow::changed`, an async notification of replacements
+//!
 //! ## Quick Example
 //!
 //! ```rust
@@ -40,9 +68,49 @@
 //! lazy.try_replace(vec![10, 11, 12]).unwrap();
 //! ```
 
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
 use arc_swap::{ArcSwap, Guard};
-use std::ops::Deref;
-use std::sync::{Arc, OnceLock};
+use std::any::Any;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::ffi::{OsStr, OsString};
+use std::ops::{Add, AddAssign, Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::ptr::NonNull;
+use std::sync::{Arc, OnceLock, Weak};
+use std::time::Duration;
+
+mod by_value;
+mod collection_ext;
+mod collections;
+#[cfg(feature = "serde")]
+mod cow_bytes;
+mod cow_str;
+mod heap_size;
+mod local;
+#[cfg(feature = "rayon")]
+mod rayon;
+#[cfg(feature = "serde")]
+mod serde;
+mod shared;
+mod try_lazy;
+mod updatable;
+
+#[cfg(feature = "serde")]
+pub use crate::serde::{as_shared, as_updatable, AsUpdatable};
+pub use by_value::ByValue;
+pub use collection_ext::UpdatableCollectionExt;
+pub use collections::{Compact, MaybeEmpty, Retainable, StaticEmpty, WithCapacity};
+#[cfg(feature = "serde")]
+pub use cow_bytes::{as_base64, as_hex};
+pub use heap_size::HeapSize;
+pub use local::{LocalAnyCow, RefCount};
+pub use shared::{SharedAnyCow, SharedRef};
+pub use try_lazy::{InitPolicy, TryLazy};
+pub use updatable::{
+    Cached, CasError, CellBuilder, ReplaceStats, UpdatableCell, UpdatableHandle, Watcher,
+};
 
 /// A supercharged container that can hold data in multiple storage formats,
 /// optimized for read-heavy, occasionally-updated scenarios.
@@ -54,8 +122,92 @@ use std::sync::{Arc, OnceLock};
 /// - **Owned**: Heap-allocated owned data via `Box<T>`
 /// - **Shared**: Reference-counted sharing via `Arc<T>`
 /// - **Updatable**: Atomic, lock-free updates via `arc-swap`
+/// - **Tracked**: `Updatable` with write counting, history and write policies
 /// - **Lazy**: Lazy initialization with atomic updates for static contexts
 ///
+/// # Payload types
+///
+/// Unlike `std::borrow::Cow<'a, B>`, where the owned form may be a different
+/// type (`Cow<str>` owns a `String`), every variant of `AnyCow` stores the
+/// same `T`, and nearly all methods require `T: ToOwned<Owned = T>`. In
+/// practice that means any `T: Clone`. `AnyCow` can't hold unsized types
+/// like `str` or `[u8]`, because `arc-swap` (backing `Updatable` and `Lazy`)
+/// and the `fn() -> T` initializer both need a sized value. Use the owned
+/// type instead: `AnyCow<String>` rather than `AnyCow<str>`, and
+/// `AnyCow<Vec<T>>` rather than `AnyCow<[T]>`. Accessors such as
+/// [`AnyCow::as_str()`] and [`AnyCow::as_slice()`] give slice views.
+///
+/// For the same reason there is no `From<&'static str>` producing a
+/// borrowed `AnyCow<str>`: a literal can't be borrowed as a `String`
+/// without copying it. When literals dominate, such as for config keys,
+/// store the `&'static str` itself; borrowing a `'static` slot holding the
+/// literal allocates nothing:
+///
+/// ```rust
+/// use anycow::AnyCow;
+///
+/// static DEFAULT_KEY: &str = "log.level";
+///
+/// let key: AnyCow<'static, &str> = AnyCow::borrowed(&DEFAULT_KEY);
+/// assert!(key.is_borrowed());
+/// assert_eq!(*key.borrow(), "log.level");
+/// ```
+///
+/// # Reference counting
+///
+/// `Shared`, `Updatable` and `Lazy` always use `std::sync::Arc`.
+/// Alternative `Arc` implementations such as `triomphe::Arc` can be
+/// converted at the boundary. [`LocalAnyCow`] is generic over its pointer
+/// through [`RefCount`], and with the default `Rc` avoids atomic reference
+/// counting altogether.
+///
+/// # Memory ordering
+///
+/// Replacing the value of an `Updatable` or `Lazy` variant publishes it
+/// the same way a `Release` store publishes data to an `Acquire` load:
+/// everything the writing thread did before
+/// [`try_replace()`](AnyCow::try_replace) returned, including constructing
+/// the new value, happens-before anything a reader does after a
+/// [`borrow()`](AnyCow::borrow) that observes the new value. A reader
+/// never sees a partially constructed value.
+///
+/// Between replacements themselves, `arc-swap` uses `SeqCst` swaps, so all
+/// threads agree on a single order of the stored values, and a thread that
+/// has observed a value never later observes an older one. A `borrow()`
+/// that runs concurrently with a replacement may still return the previous
+/// value. The orderings are fixed by `arc-swap`'s reference-counting
+/// protocol, which relies on them to keep loaded values alive, so they
+/// can't be weakened per call; there is no `Relaxed` variant of
+/// `try_replace()`. Code that needs to order other memory operations
+/// against a replacement should synchronize through the value itself or
+/// through its own atomics.
+///
+/// # Lazy initialization side effects
+///
+/// Anything that reads the value of a `Lazy` variant runs its initializer
+/// if it hasn't run yet. That includes the trait impls, not only
+/// [`borrow()`](AnyCow::borrow): comparing (`==`, `<`, sorting), hashing
+/// (inserting into a `HashMap`/`HashSet`), `Display` and `Clone` all
+/// initialize. Sorting a `Vec` of lazies therefore initializes every one of
+/// them. Use [`AnyCow::is_initialized()`] to check beforehand; the `Debug`
+/// impl and [`AnyCow::debug_value()`] never initialize.
+///
+/// Because `Updatable` and `Lazy` values can be replaced, their hash and
+/// ordering can change while they are stored in a `HashMap`, `HashSet` or
+/// `BTreeMap`, corrupting the collection. Wrap keys in [`ByValue`] to
+/// freeze them first.
+///
+/// # Memory layout
+///
+/// Every `AnyCow` is four words (32 bytes on 64-bit targets), whatever the
+/// variant. The size is set by `Lazy`, which keeps its `OnceLock<ArcSwap>`
+/// and the `fn` pointer of its initializer inline; `Updatable` keeps its
+/// one-word `ArcSwap` inline too, since boxing it wouldn't shrink the enum
+/// below `Lazy`. The [`UpdatableCell`] behind `Tracked` is several words
+/// large, so it lives in its own heap allocation: creating a `Tracked`
+/// container costs one extra allocation, and its reads go through one more
+/// pointer.
+///
 /// # Examples
 ///
 /// ```rust
@@ -106,6 +258,18 @@ where
     /// data, caches, or any shared state that needs occasional updates.
     Updatable(ArcSwap<T>),
 
+    /// Atomically updatable data whose writes are tracked.
+    ///
+    /// Reads and writes work like `Updatable`, but every write goes through
+    /// an [`UpdatableCell`], which counts replacements for
+    /// [`try_replace_tracked()`](AnyCow::try_replace_tracked) and applies
+    /// the policies of constructors
+    /// such as [`updatable_with_history()`](AnyCow::updatable_with_history)
+    /// and [`updatable_validated()`](AnyCow::updatable_validated). Created
+    /// by [`updatable_tracked()`](AnyCow::updatable_tracked) and those
+    /// constructors. The cell is boxed to keep the enum small.
+    Tracked(Box<UpdatableCell<T>>),
+
     /// Lazy initialization with atomic updates.
     ///
     /// This variant combines lazy initialization with atomic updates.
@@ -146,11 +310,51 @@ where
         AnyCow::Borrowed(value)
     }
 
+    /// Creates a `Borrowed` `AnyCow` of an empty, `'static` value.
+    ///
+    /// Works for every [`StaticEmpty`] payload: `Vec`, `String`,
+    /// `VecDeque`, `BTreeMap`, `BTreeSet` and `Option`. Nothing is
+    /// allocated; the container borrows a shared empty value, which makes
+    /// this a zero-cost default for `const` and `static` items and a cheap
+    /// sentinel in hot paths. Mutating it through [`to_mut()`](Self::to_mut)
+    /// converts it to `Owned` as usual.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// static NO_TAGS: AnyCow<'static, Vec<String>> = AnyCow::empty();
+    ///
+    /// assert!(NO_TAGS.is_borrowed());
+    /// assert!(NO_TAGS.borrow().is_empty());
+    ///
+    /// let mut name: AnyCow<String> = AnyCow::empty();
+    /// name.to_mut().push_str("set later");
+    /// assert!(name.is_owned());
+    /// ```
+    pub const fn empty() -> Self
+    where
+        T: StaticEmpty,
+    {
+        AnyCow::Borrowed(T::EMPTY)
+    }
+
     /// Creates a new `AnyCow` with owned data stored in a `Box<T>`.
     ///
     /// The data is moved into a heap-allocated box and can be mutated
     /// via [`to_mut()`](Self::to_mut).
     ///
+    /// This isn't a `const fn` because `Box::new` isn't; use
+    /// [`lazy()`](Self::lazy) for `const` and `static` items.
+    ///
+    /// The box always comes from the global allocator. `Box<T, A>` is
+    /// still unstable (`allocator_api`), and an allocator parameter on the
+    /// `Owned` variant would have to appear on every `AnyCow` type. Data
+    /// placed in an arena or pool can be wrapped with
+    /// [`borrowed()`](Self::borrowed) instead, which ties the container to
+    /// the arena's lifetime without copying.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -181,12 +385,51 @@ where
         AnyCow::Shared(value)
     }
 
+    /// Creates a new `Shared` whose value holds a `Weak` reference to its
+    /// own `Arc`.
+    ///
+    /// Built on `Arc::new_cyclic`: `f` receives a `Weak` pointing to the
+    /// allocation that will hold its result, so tree and graph nodes can
+    /// refer back to themselves. Upgrading the `Weak` inside `f` returns
+    /// `None`, since the value doesn't exist yet.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use std::sync::{Arc, Weak};
+    ///
+    /// #[derive(Clone)]
+    /// struct Node {
+    ///     name: String,
+    ///     this: Weak<Node>,
+    /// }
+    ///
+    /// let node = AnyCow::shared_cyclic(|this| Node {
+    ///     name: String::from("root"),
+    ///     this: this.clone(),
+    /// });
+    ///
+    /// let this = node.borrow().this.upgrade().unwrap();
+    /// assert!(Arc::ptr_eq(&this, &node.to_arc()));
+    /// ```
+    pub fn shared_cyclic<F>(f: F) -> Self
+    where
+        F: FnOnce(&Weak<T>) -> T,
+    {
+        AnyCow::Shared(Arc::new_cyclic(f))
+    }
+
     /// Creates a new `AnyCow` with atomically updatable data.
     ///
     /// This variant uses `arc-swap` for lock-free, atomic updates
     /// while allowing concurrent reads. Perfect for configuration
     /// data, caches, or shared state with infrequent updates.
     ///
+    /// This isn't a `const fn` because allocating the `Arc` isn't possible
+    /// in a `const` context; use [`lazy()`](Self::lazy) for `const` and
+    /// `static` items instead.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -202,7 +445,289 @@ where
     /// assert_eq!(*cow.borrow(), vec![4, 5, 6]);
     /// ```
     pub fn updatable(value: T) -> Self {
-        AnyCow::Updatable(ArcSwap::from(Arc::new(value)))
+        AnyCow::Updatable(ArcSwap::from_pointee(value))
+    }
+
+    /// Creates a new `Tracked` container with atomically updatable data.
+    ///
+    /// Works like [`updatable()`](Self::updatable), but counts every
+    /// replacement, which
+    /// [`try_replace_tracked()`](Self::try_replace_tracked) and
+    /// [`retained_count()`](Self::retained_count) rely on. Its watchers
+    /// are also woken per container rather than through the process-wide
+    /// wakeup of plain values, see [`watch()`](Self::watch). The bookkeeping
+    /// costs one extra allocation per container and a few atomic operations
+    /// per write; plain `Updatable` containers don't pay for it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let config = AnyCow::updatable_tracked(1);
+    /// let stats = config.try_replace_tracked(2).unwrap();
+    /// assert_eq!(stats.concurrent_writes(), 0);
+    ///
+    /// assert!(AnyCow::updatable(1).try_replace_tracked(2).is_err());
+    /// ```
+    pub fn updatable_tracked(value: T) -> Self {
+        AnyCow::Tracked(Box::new(UpdatableCell::new(value)))
+    }
+
+    /// Creates a `Tracked` view over an `ArcSwap` owned elsewhere.
+    ///
+    /// The `ArcSwap` is adopted, not copied: every `AnyCow` created from the
+    /// same `Arc<ArcSwap<T>>`, and any other code holding it, reads and
+    /// writes the same atomic cell and sees the others' updates. This makes
+    /// `AnyCow` a thin facade over existing `arc-swap` infrastructure.
+    ///
+    /// [`watch()`](Self::watch) only wakes up for writes made through this
+    /// particular container; writes made directly on the `ArcSwap` or
+    /// through another container are visible to readers but don't notify
+    /// its watchers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use arc_swap::ArcSwap;
+    /// use std::sync::Arc;
+    ///
+    /// let cell = Arc::new(ArcSwap::from_pointee(String::from("v1")));
+    /// let a = AnyCow::from_arc_swap(cell.clone());
+    /// let b = AnyCow::from_arc_swap(cell.clone());
+    ///
+    /// a.try_replace(String::from("v2")).unwrap();
+    /// assert_eq!(*b.borrow(), "v2");
+    ///
+    /// cell.store(Arc::new(String::from("v3")));
+    /// assert_eq!(*a.borrow(), "v3");
+    /// ```
+    pub fn from_arc_swap(value: Arc<arc_swap::ArcSwap<T>>) -> AnyCow<'static, T>
+    where
+        T: 'static,
+    {
+        AnyCow::Tracked(Box::new(UpdatableCell::from_arc_swap(value)))
+    }
+
+    /// Creates a new `Tracked` container whose atomic pointer is padded to a
+    /// full cache line.
+    ///
+    /// Many `Updatable` containers placed next to each other, such as an
+    /// array of per-shard counters, share cache lines; a write to one then
+    /// invalidates the line for cores reading its neighbours. A padded
+    /// container keeps its hot state on a cache line of its own, at the
+    /// cost of one extra allocation of 64 or 128 bytes. It otherwise
+    /// behaves exactly like [`updatable_tracked()`](Self::updatable_tracked).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let shards: Vec<AnyCow<u64>> = (0..8).map(|_| AnyCow::updatable_padded(0)).collect();
+    /// shards[3].try_replace(42).unwrap();
+    /// assert_eq!(*shards[3].borrow(), 42);
+    /// ```
+    pub fn updatable_padded(value: T) -> Self {
+        AnyCow::Tracked(Box::new(UpdatableCell::padded(value)))
+    }
+
+    /// Creates a new `Tracked` container that remembers up to `capacity`
+    /// previous values.
+    ///
+    /// Every successful replacement pushes the value being replaced into a
+    /// small ring buffer, retrievable via [`history()`](Self::history).
+    /// Once the buffer is full, the oldest snapshot is discarded. Plain
+    /// [`updatable()`](Self::updatable) containers don't pay for this.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let config = AnyCow::updatable_with_history(1, 2);
+    /// config.try_replace(2).unwrap();
+    /// config.try_replace(3).unwrap();
+    /// config.try_replace(4).unwrap();
+    ///
+    /// let history: Vec<i32> = config.history().iter().map(|v| **v).collect();
+    /// assert_eq!(history, vec![2, 3]);
+    /// assert_eq!(*config.borrow(), 4);
+    /// ```
+    pub fn updatable_with_history(value: T, capacity: usize) -> Self {
+        AnyCow::Tracked(Box::new(UpdatableCell::with_history(value, capacity)))
+    }
+
+    /// Creates a new `Updatable` holding an empty collection with room for
+    /// at least `capacity` elements.
+    ///
+    /// Copy-on-write updates clone the value, and a clone only keeps the
+    /// capacity it needs, so the reservation pays off when the collection
+    /// is moved rather than copied, such as when the container is consumed
+    /// with [`into_owned()`](Self::into_owned) once nothing else shares it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let buffer: AnyCow<Vec<u8>> = AnyCow::updatable_with_capacity(4096);
+    /// assert!(buffer.borrow().is_empty());
+    ///
+    /// let mut bytes = buffer.into_owned();
+    /// assert!(bytes.capacity() >= 4096);
+    /// bytes.extend_from_slice(b"no reallocation");
+    /// ```
+    pub fn updatable_with_capacity(capacity: usize) -> Self
+    where
+        T: WithCapacity,
+    {
+        AnyCow::Updatable(ArcSwap::from_pointee(T::with_capacity(capacity)))
+    }
+
+    /// Creates a new `Tracked` container that reloads its value once it's
+    /// older than `ttl`.
+    ///
+    /// Reads through [`borrow()`](Self::borrow) (and everything built on it)
+    /// serve the cached value until `ttl` has elapsed since the last
+    /// reload. The first read after that calls `refresh` and stores the
+    /// result atomically; only one thread refreshes at a time, and readers
+    /// arriving in the meantime get the stale value instead of waiting.
+    /// This suits configuration fetched over the network, where slightly
+    /// stale data is fine but a blocked reader isn't.
+    ///
+    /// The container is an ordinary `Tracked` one otherwise:
+    /// [`try_replace()`](Self::try_replace) works, though it doesn't reset
+    /// the timer. Clones don't refresh.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use std::sync::atomic::{AtomicU32, Ordering};
+    /// use std::time::Duration;
+    ///
+    /// static FETCHES: AtomicU32 = AtomicU32::new(0);
+    ///
+    /// let config = AnyCow::updatable_with_refresh(0, Duration::ZERO, || {
+    ///     FETCHES.fetch_add(1, Ordering::SeqCst) + 1
+    /// });
+    ///
+    /// // A zero TTL refreshes on every read
+    /// assert_eq!(*config.borrow(), 1);
+    /// assert_eq!(*config.borrow(), 2);
+    ///
+    /// let cached = AnyCow::updatable_with_refresh(0, Duration::from_secs(60), || 1);
+    /// // The initial value counts as fresh
+    /// assert_eq!(*cached.borrow(), 0);
+    /// ```
+    pub fn updatable_with_refresh<F>(init: T, ttl: Duration, refresh: F) -> Self
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        AnyCow::Tracked(Box::new(UpdatableCell::with_refresh(init, ttl, refresh)))
+    }
+
+    /// Creates a new `Tracked` container whose replacements must pass
+    /// `validator`.
+    ///
+    /// Every write runs the validator first and leaves the value untouched
+    /// if it returns `Err`, which enforces the invariants of hot-reloaded
+    /// configuration in one place instead of at every call site. This
+    /// includes lower-level writes through [`as_updatable()`](Self::as_updatable)
+    /// and read-copy-update helpers like [`retain()`](Self::retain); each
+    /// reports the rejection through its own error. `init` is assumed to be
+    /// valid and isn't checked.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let timeout_ms = AnyCow::updatable_validated(500u64, |&ms| match ms {
+    ///     1..=60_000 => Ok(()),
+    ///     _ => Err(format!("timeout {ms}ms is out of range")),
+    /// });
+    ///
+    /// assert!(timeout_ms.try_replace(1_000).is_ok());
+    /// assert!(timeout_ms.try_replace(0).is_err());
+    /// assert_eq!(*timeout_ms.borrow(), 1_000);
+    /// ```
+    pub fn updatable_validated<V, E>(init: T, validator: V) -> Self
+    where
+        V: Fn(&T) -> Result<(), E> + Send + Sync + 'static,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        AnyCow::Tracked(Box::new(UpdatableCell::validated(init, validator)))
+    }
+
+    /// Creates a new `Tracked` container that applies at most one
+    /// replacement per `min_interval`, protecting watchers and derived
+    /// state from update storms.
+    ///
+    /// Replacements arriving sooner than `min_interval` after the last
+    /// applied one are dropped: [`try_replace()`](Self::try_replace) and
+    /// every other write, including those through
+    /// [`as_updatable()`](Self::as_updatable) and read-copy-update helpers
+    /// like [`retain()`](Self::retain), still succeed, but the value is
+    /// discarded. Nothing is deferred and applied later, so the last value
+    /// of a burst may be lost; a source that must converge should resend
+    /// its current state periodically. The first replacement is always
+    /// applied. To combine the interval with a validator or a history,
+    /// use [`UpdatableCell::builder()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use std::time::Duration;
+    ///
+    /// let reading = AnyCow::updatable_debounced(0, Duration::from_secs(60));
+    /// reading.try_replace(1).unwrap();
+    /// // Too soon after the last update: accepted but dropped
+    /// reading.try_replace(2).unwrap();
+    /// assert_eq!(*reading.borrow(), 1);
+    /// ```
+    pub fn updatable_debounced(init: T, min_interval: Duration) -> Self {
+        AnyCow::Tracked(Box::new(UpdatableCell::debounced(init, min_interval)))
+    }
+
+    /// Creates a new `Tracked` container together with a channel receiving
+    /// every replacement.
+    ///
+    /// Each successful store, whether through
+    /// [`try_replace()`](Self::try_replace) or any other replacing method,
+    /// sends the new `Arc` to the receiver, so consumers can process
+    /// updates on their own schedule instead of blocking on
+    /// [`watch()`](Self::watch). Unlike a watcher, nothing is coalesced:
+    /// the receiver sees every value, in store order for a single writer.
+    /// The channel is unbounded, so writers never block; values queue up
+    /// while the consumer lags behind. The initial value isn't sent.
+    ///
+    /// Dropping the receiver is harmless: replacements keep working and
+    /// their values are no longer sent anywhere. Clones of the container
+    /// get their own storage and don't send to the channel.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let (config, updates) = AnyCow::updatable_channel(1);
+    ///
+    /// let consumer = std::thread::spawn(move || updates.iter().map(|v| *v).collect::<Vec<_>>());
+    /// config.try_replace(2).unwrap();
+    /// config.try_replace(3).unwrap();
+    /// drop(config);
+    ///
+    /// assert_eq!(consumer.join().unwrap(), vec![2, 3]);
+    /// ```
+    #[cfg(feature = "crossbeam")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "crossbeam")))]
+    pub fn updatable_channel(init: T) -> (Self, crossbeam_channel::Receiver<Arc<T>>) {
+        let (cell, receiver) = UpdatableCell::with_channel(init);
+        (AnyCow::Tracked(Box::new(cell)), receiver)
     }
 
     /// Creates a new `AnyCow` with lazy initialization and atomic updates.
@@ -237,6 +762,76 @@ where
         }
     }
 
+    /// Creates a new `AnyCow` with lazy initialization from a capturing closure.
+    ///
+    /// Works like [`lazy()`](Self::lazy), but accepts any thread-safe
+    /// closure, so the initializer can capture configuration decided at
+    /// runtime. The closure is kept in the [`UpdatableCell`] of a `Tracked`
+    /// container, so the `fn() -> T` of the `Lazy` variant stays as it is.
+    /// It runs exactly once, even when several threads touch the value for
+    /// the first time concurrently, and the value then supports
+    /// [`try_replace()`](Self::try_replace) like any other updatable value.
+    ///
+    /// Boxing the closure means this isn't a `const fn`. For `static` items
+    /// that need runtime configuration, wrap the container in a
+    /// `std::sync::OnceLock` or read the configuration from a plain
+    /// [`lazy()`](Self::lazy) initializer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let prefix = String::from("app");
+    /// let name = AnyCow::lazy_boxed(move || format!("{prefix}-worker"));
+    ///
+    /// assert!(!name.is_initialized());
+    /// assert_eq!(*name.borrow(), "app-worker");
+    /// assert!(name.is_initialized());
+    /// ```
+    pub fn lazy_boxed<F>(init: F) -> Self
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        AnyCow::Tracked(Box::new(UpdatableCell::lazy(init)))
+    }
+
+    /// Adopts a `std::sync::OnceLock` into a `Lazy` variant, keeping its
+    /// initialization state.
+    ///
+    /// If `cell` is already initialized, the container starts out
+    /// initialized with that value and `init` never runs; otherwise `init`
+    /// runs on first access, as with [`lazy()`](Self::lazy). This lets
+    /// existing lazy values move to `AnyCow` incrementally and gain
+    /// [`try_replace()`](Self::try_replace). A `LazyLock` can't be adopted
+    /// the same way, since stable Rust offers no way to take its value or
+    /// initializer out.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use std::sync::OnceLock;
+    ///
+    /// let cell = OnceLock::new();
+    /// cell.set(String::from("loaded")).unwrap();
+    ///
+    /// let name = AnyCow::from_once_lock(cell, || String::from("default"));
+    /// assert!(name.is_initialized());
+    /// assert_eq!(*name.borrow(), "loaded");
+    ///
+    /// let empty = AnyCow::from_once_lock(OnceLock::new(), || String::from("default"));
+    /// assert!(!empty.is_initialized());
+    /// assert_eq!(*empty.borrow(), "default");
+    /// ```
+    pub fn from_once_lock(cell: OnceLock<T>, init: fn() -> T) -> Self {
+        let data = match cell.into_inner() {
+            Some(value) => OnceLock::from(ArcSwap::from_pointee(value)),
+            None => OnceLock::new(),
+        };
+        AnyCow::Lazy { data, init }
+    }
+
     /// Returns `true` if this `AnyCow` contains a borrowed reference.
     ///
     /// # Examples
@@ -291,7 +886,8 @@ where
         matches!(self, AnyCow::Shared(_))
     }
 
-    /// Returns `true` if this `AnyCow` contains updatable data.
+    /// Returns `true` if this `AnyCow` contains updatable data, either
+    /// `Updatable` or `Tracked`.
     ///
     /// # Examples
     ///
@@ -305,7 +901,7 @@ where
     /// assert!(!cow.is_updatable());
     /// ```
     pub const fn is_updatable(&self) -> bool {
-        matches!(self, AnyCow::Updatable(_))
+        matches!(self, AnyCow::Updatable(_) | AnyCow::Tracked(_))
     }
 
     /// Returns `true` if this `AnyCow` contains lazy data.
@@ -325,106 +921,71 @@ where
         matches!(self, AnyCow::Lazy { .. })
     }
 
-    /// Returns a mutable reference to the owned data.
+    /// Returns `true` if reading this `AnyCow` goes through a guard.
     ///
-    /// If the data is not already owned, this method will clone it
-    /// (following Clone-on-Write semantics) and convert the container
-    /// to the `Owned` variant.
+    /// `Borrowed`, `Owned` and `Shared` hand out direct references, while
+    /// `Updatable`, `Tracked` and `Lazy` have to load an `arc-swap` guard on every
+    /// [`borrow()`](Self::borrow). Hot paths can assert on this to catch
+    /// a guarded variant sneaking in where a direct read was expected.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use anycow::AnyCow;
     ///
-    /// let hello = String::from("hello");
-    /// let mut cow = AnyCow::borrowed(&hello);
-    /// assert!(cow.is_borrowed());
-    ///
-    /// // This will clone the data and make it owned
-    /// let mutable_ref = cow.to_mut();
-    /// *mutable_ref = String::from("world");
+    /// let cow = AnyCow::owned(String::from("hello"));
+    /// assert!(!cow.is_guarded_read());
     ///
-    /// assert!(cow.is_owned());
-    /// assert_eq!(*cow.borrow(), "world");
+    /// let cow = AnyCow::updatable(String::from("hello"));
+    /// assert!(cow.is_guarded_read());
     /// ```
-    pub fn to_mut(&mut self) -> &mut T {
-        match self {
-            AnyCow::Borrowed(value) => {
-                *self = AnyCow::Owned(Box::new(value.to_owned()));
-                match self {
-                    AnyCow::Owned(value) => value,
-                    _ => unreachable!(),
-                }
-            }
-            AnyCow::Owned(value) => value,
-            AnyCow::Shared(value) => {
-                *self = AnyCow::Owned(Box::new(value.as_ref().to_owned()));
-                match self {
-                    AnyCow::Owned(value) => value,
-                    _ => unreachable!(),
-                }
-            }
-            AnyCow::Updatable(value) => {
-                let owned = value.load().as_ref().to_owned();
-                *self = AnyCow::Owned(Box::new(owned));
-                match self {
-                    AnyCow::Owned(value) => value,
-                    _ => unreachable!(),
-                }
-            }
-            AnyCow::Lazy { data, init } => {
-                let arc_swap = data.get_or_init(|| ArcSwap::from(Arc::new(init())));
-                let owned = arc_swap.load().as_ref().to_owned();
-                *self = AnyCow::Owned(Box::new(owned));
-                match self {
-                    AnyCow::Owned(value) => value,
-                    _ => unreachable!(),
-                }
-            }
-        }
+    pub const fn is_guarded_read(&self) -> bool {
+        matches!(
+            self,
+            AnyCow::Updatable(_) | AnyCow::Tracked(_) | AnyCow::Lazy { .. }
+        )
     }
 
-    /// Converts this `AnyCow` into owned data.
+    /// Returns `false` for a `Lazy` variant whose initializer hasn't run yet.
     ///
-    /// This method consumes the container and returns the owned data,
-    /// cloning if necessary. For `Arc` data, it will try to unwrap
-    /// the `Arc` if there's only one reference, otherwise it will clone.
+    /// Every other variant always holds a value and returns `true`. This is
+    /// a cheap check that never triggers initialization, unlike reading the
+    /// value or using the comparison and hashing impls.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use anycow::AnyCow;
-    /// use std::sync::Arc;
     ///
-    /// let hello = String::from("hello");
-    /// let cow = AnyCow::borrowed(&hello);
-    /// let owned: String = cow.into_owned();
-    /// assert_eq!(owned, "hello");
+    /// let cow = AnyCow::lazy(|| vec![1, 2, 3]);
+    /// assert!(!cow.is_initialized());
     ///
-    /// let cow = AnyCow::shared(Arc::new(42));
-    /// let owned: i32 = cow.into_owned();
-    /// assert_eq!(owned, 42);
+    /// let _ = cow.borrow();
+    /// assert!(cow.is_initialized());
+    ///
+    /// assert!(AnyCow::owned(vec![1]).is_initialized());
     /// ```
-    pub fn into_owned(self) -> T {
+    pub fn is_initialized(&self) -> bool {
         match self {
-            AnyCow::Borrowed(value) => value.to_owned(),
-            AnyCow::Owned(value) => *value,
-            AnyCow::Shared(value) => {
-                Arc::try_unwrap(value).unwrap_or_else(|arc| arc.as_ref().to_owned())
-            }
-            AnyCow::Updatable(value) => value.load().as_ref().to_owned(),
-            AnyCow::Lazy { data, init } => {
-                let arc_swap = data.get_or_init(|| ArcSwap::from(Arc::new(init())));
-                arc_swap.load().as_ref().to_owned()
-            }
+            AnyCow::Lazy { data, .. } => data.get().is_some(),
+            AnyCow::Tracked(cell) => cell.is_initialized(),
+            _ => true,
         }
     }
 
-    /// Returns a reference to the contained data.
+    /// Returns the number of strong references to the shared value.
     ///
-    /// This method provides unified access to the data regardless of
-    /// the storage variant. For the `Updatable` variant, this returns
-    /// a guard that ensures the data remains valid during access.
+    /// For `Shared` variants this is `Arc::strong_count`; for `Updatable`
+    /// and initialized `Lazy` variants it is the count of the current
+    /// value's `Arc`, which includes the container's own reference and any
+    /// snapshots obtained through [`to_arc()`](Self::to_arc). A count of 1
+    /// means [`into_owned()`](Self::into_owned) can move the value out
+    /// instead of cloning it. Returns `None` for `Borrowed` and `Owned`
+    /// data, and for a `Lazy` that hasn't been initialized; this never runs
+    /// the initializer.
+    ///
+    /// The count is a snapshot: other threads may clone or drop references
+    /// at any time, so it can be stale by the time it is used.
     ///
     /// # Examples
     ///
@@ -432,37 +993,2355 @@ where
     /// use anycow::AnyCow;
     /// use std::sync::Arc;
     ///
-    /// let borrowed = AnyCow::borrowed(&"hello");
-    /// let owned = AnyCow::owned(String::from("world"));
-    /// let shared = AnyCow::shared(Arc::new(42));
+    /// let data = Arc::new(vec![1, 2, 3]);
+    /// let cow = AnyCow::shared(data.clone());
+    /// assert_eq!(cow.ref_count(), Some(2));
     ///
-    /// assert_eq!(*borrowed.borrow(), "hello");
-    /// assert_eq!(*owned.borrow(), "world");
-    /// assert_eq!(*shared.borrow(), 42);
+    /// drop(data);
+    /// assert_eq!(cow.ref_count(), Some(1));
+    ///
+    /// assert_eq!(AnyCow::owned(1).ref_count(), None);
     /// ```
-    pub fn borrow(&self) -> AnyCowRef<T> {
+    #[doc(alias = "shared_count")]
+    pub fn ref_count(&self) -> Option<usize> {
         match self {
-            AnyCow::Borrowed(value) => AnyCowRef::Direct(value),
-            AnyCow::Owned(value) => AnyCowRef::Direct(&**value),
-            AnyCow::Shared(value) => AnyCowRef::Direct(value),
-            AnyCow::Updatable(value) => AnyCowRef::Guarded(value.load()),
-            AnyCow::Lazy { data, init } => {
-                let arc_swap = data.get_or_init(|| ArcSwap::from(Arc::new(init())));
-                AnyCowRef::Guarded(arc_swap.load())
-            }
+            AnyCow::Shared(value) => Some(Arc::strong_count(value)),
+            AnyCow::Updatable(value) => Some(Arc::strong_count(&value.load())),
+            AnyCow::Tracked(cell) => cell.is_initialized().then(|| cell.strong_count()),
+            AnyCow::Lazy { data, .. } => data.get().map(|value| Arc::strong_count(&value.load())),
+            AnyCow::Borrowed(_) | AnyCow::Owned(_) => None,
         }
     }
 
-    /// Attempts to atomically replace the value in an `Updatable` or `Lazy` variant.
+    /// Returns how many borrows and snapshots were still holding the
+    /// previous value when a `Tracked` container was last replaced.
     ///
-    /// This method succeeds if the container is of the `Updatable` or `Lazy` variant.
-    /// The replacement is atomic and lock-free, making it perfect for
-    /// concurrent scenarios. For `Lazy` variants, this will initialize the
-    /// data if it hasn't been accessed before.
+    /// Replacing the value never waits for readers: a guard from
+    /// [`borrow()`](Self::borrow) or an `Arc` from [`to_arc()`](Self::to_arc)
+    /// that is alive during [`try_replace()`](Self::try_replace) keeps the
+    /// old value in memory until it is dropped. A nonzero count after a
+    /// replacement on an otherwise idle container usually means a guard
+    /// is being held across the update, often by the very code doing it,
+    /// so drop guards before replacing. References from every thread
+    /// count, so under concurrent reads the number is only a hint.
+    ///
+    /// Returns `None` for other variants, including plain `Updatable` and
+    /// `Lazy` ones, which don't count, and `Some(0)` before the first
+    /// replacement.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let cow = AnyCow::updatable_tracked(vec![0u8; 1024]);
+    ///
+    /// let guard = cow.borrow();
+    /// cow.try_replace(vec![1; 1024]).unwrap();
+    /// assert_eq!(cow.retained_count(), Some(1)); // the old buffer is still alive
+    /// drop(guard);
+    ///
+    /// cow.try_replace(vec![2; 1024]).unwrap();
+    /// assert_eq!(cow.retained_count(), Some(0));
+    /// ```
+    #[doc(alias = "borrow_count")]
+    pub fn retained_count(&self) -> Option<usize> {
+        match self {
+            AnyCow::Tracked(cell) => Some(cell.retained_count()),
+            _ => None,
+        }
+    }
+
+    /// Feeds the current value into `state`, hashing one consistent
+    /// snapshot.
+    ///
+    /// An `Updatable` or `Lazy` is snapshotted into an `Arc` once and
+    /// every byte fed to `state` comes from that snapshot, even if the
+    /// value is replaced while hashing, including by the hasher itself.
+    /// The `Hash` impl gives the same guarantee through a guard;
+    /// `stable_hash` makes it explicit for code that hashes volatile
+    /// variants on purpose. Later replacements aren't reflected in the
+    /// result, so a hash computed this way doesn't make an `Updatable` a
+    /// sound map key; use [`ByValue`] for that.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::{Hash, Hasher};
+    ///
+    /// let cow = AnyCow::updatable(String::from("v1"));
+    /// let mut state = DefaultHasher::new();
+    /// cow.stable_hash(&mut state);
+    ///
+    /// let mut expected = DefaultHasher::new();
+    /// String::from("v1").hash(&mut expected);
+    /// assert_eq!(state.finish(), expected.finish());
+    /// ```
+    pub fn stable_hash<H: std::hash::Hasher>(&self, state: &mut H)
+    where
+        T: std::hash::Hash,
+    {
+        match self.as_direct() {
+            Some(value) => std::hash::Hash::hash(value, state),
+            None => std::hash::Hash::hash(&*self.to_arc(), state),
+        }
+    }
+
+    /// Returns a mutable reference to the owned data.
+    ///
+    /// If the data is not already owned, this method will clone it
+    /// (following Clone-on-Write semantics) and convert the container
+    /// to the `Owned` variant. A `Shared` variant whose `Arc` nothing else
+    /// references is not cloned: the value is moved out of the `Arc`
+    /// instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let hello = String::from("hello");
+    /// let mut cow = AnyCow::borrowed(&hello);
+    /// assert!(cow.is_borrowed());
+    ///
+    /// // This will clone the data and make it owned
+    /// let mutable_ref = cow.to_mut();
+    /// *mutable_ref = String::from("world");
+    ///
+    /// assert!(cow.is_owned());
+    /// assert_eq!(*cow.borrow(), "world");
+    /// ```
+    pub fn to_mut(&mut self) -> &mut T {
+        self.to_mut_tracked().0
+    }
+
+    /// Like [`to_mut()`](Self::to_mut), but also reports what the data had
+    /// to be materialized from.
+    ///
+    /// Hot paths can check the [`MaterializedFrom`] to detect unexpected
+    /// clones, such as a value that should arrive `Owned` but keeps
+    /// arriving `Shared`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::{AnyCow, MaterializedFrom};
+    /// use std::sync::Arc;
+    ///
+    /// let data = Arc::new(vec![1, 2, 3]);
+    /// let mut cow = AnyCow::shared(data.clone());
+    /// let (items, from) = cow.to_mut_tracked();
+    /// items.push(4);
+    /// assert_eq!(from, MaterializedFrom::ClonedFromShared);
+    /// assert!(from.cloned());
+    ///
+    /// let (_, from) = cow.to_mut_tracked();
+    /// assert_eq!(from, MaterializedFrom::AlreadyOwned);
+    /// ```
+    pub fn to_mut_tracked(&mut self) -> (&mut T, MaterializedFrom) {
+        let mut from = MaterializedFrom::AlreadyOwned;
+        if self.unwrap_unique_shared() {
+            from = MaterializedFrom::MovedFromShared;
+        }
+        // Runs at most twice: the second pass always finds `Owned`.
+        loop {
+            from = match self {
+                AnyCow::Owned(value) => return (value, from),
+                AnyCow::Borrowed(_) => MaterializedFrom::ClonedFromBorrowed,
+                AnyCow::Shared(_) => MaterializedFrom::ClonedFromShared,
+                AnyCow::Updatable(_) | AnyCow::Tracked(_) => MaterializedFrom::ClonedFromUpdatable,
+                AnyCow::Lazy { .. } => MaterializedFrom::ClonedFromLazy,
+            };
+            let owned = T::to_owned(&self.borrow());
+            *self = AnyCow::Owned(Box::new(owned));
+        }
+    }
+
+    /// Installs an already boxed value, turning the container into `Owned`.
+    ///
+    /// The `Box` becomes the new storage as is, so nothing is moved or
+    /// allocated; this lets an object pool hand out recycled boxes. Whatever
+    /// the container held before is dropped, whichever variant it was.
+    /// Unlike [`try_replace()`](Self::try_replace), this changes the
+    /// container itself rather than the value behind an `Updatable`, so
+    /// other clones of it are unaffected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let template = vec![0u8; 4];
+    /// let mut buffer = AnyCow::borrowed(&template);
+    /// let recycled: Box<Vec<u8>> = Box::new(Vec::with_capacity(1024));
+    /// let address: *const Vec<u8> = &*recycled;
+    ///
+    /// buffer.set_owned(recycled);
+    /// assert!(buffer.is_owned());
+    /// assert!(std::ptr::eq(buffer.as_direct().unwrap(), address));
+    /// ```
+    pub fn set_owned(&mut self, value: Box<T>) {
+        *self = AnyCow::Owned(value);
+    }
+
+    /// Returns a mutable reference to `Owned` data, or `Err(NotOwned)` for
+    /// every other variant.
+    ///
+    /// Unlike [`to_mut()`](Self::to_mut), this never clones: code that
+    /// expects to own its data already can say so, and fails loudly
+    /// instead of silently copying data that unexpectedly arrived as
+    /// `Borrowed` or `Shared`. The container is left unchanged on error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::{AnyCow, NotOwned};
+    /// use std::sync::Arc;
+    ///
+    /// let mut buffer = AnyCow::owned(vec![1, 2]);
+    /// buffer.owned_mut().unwrap().push(3);
+    /// assert_eq!(*buffer.borrow(), vec![1, 2, 3]);
+    ///
+    /// let mut shared = AnyCow::shared(Arc::new(vec![1, 2]));
+    /// assert_eq!(shared.owned_mut().unwrap_err(), NotOwned);
+    /// assert!(shared.is_shared());
+    /// ```
+    pub fn owned_mut(&mut self) -> Result<&mut T, NotOwned> {
+        match self {
+            AnyCow::Owned(value) => Ok(value),
+            _ => Err(NotOwned),
+        }
+    }
+
+    /// Converts this `AnyCow` into owned data.
+    ///
+    /// This method consumes the container and returns the owned data,
+    /// cloning if necessary. For `Arc` data, it will try to unwrap
+    /// the `Arc` if there's only one reference, otherwise it will clone.
+    /// That includes the current value of an `Updatable` or initialized
+    /// `Lazy`, which is only cloned while other clones of the container or
+    /// snapshots of the value are alive. An uninitialized `Lazy` runs its
+    /// initializer and returns the result.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use std::sync::Arc;
+    ///
+    /// let hello = String::from("hello");
+    /// let cow = AnyCow::borrowed(&hello);
+    /// let owned: String = cow.into_owned();
+    /// assert_eq!(owned, "hello");
+    ///
+    /// let cow = AnyCow::shared(Arc::new(42));
+    /// let owned: i32 = cow.into_owned();
+    /// assert_eq!(owned, 42);
+    /// ```
+    pub fn into_owned(self) -> T {
+        match self {
+            AnyCow::Borrowed(value) => value.to_owned(),
+            AnyCow::Owned(value) => *value,
+            AnyCow::Shared(value) => unwrap_or_to_owned(value),
+            AnyCow::Updatable(value) => unwrap_or_to_owned(value.into_inner()),
+            AnyCow::Tracked(cell) => unwrap_or_to_owned(cell.into_inner()),
+            AnyCow::Lazy { data, init } => match data.into_inner() {
+                Some(value) => unwrap_or_to_owned(value.into_inner()),
+                None => init(),
+            },
+        }
+    }
+
+    /// Converts this `AnyCow` into owned data, unless that would mean
+    /// cloning data behind an `Arc`.
+    ///
+    /// [`into_owned()`](Self::into_owned) silently deep-clones shared data;
+    /// this hands the `Arc` back instead, so the caller decides whether the
+    /// clone is worth it or keeps sharing. `Shared`, `Updatable` and
+    /// initialized `Lazy` variants are unwrapped with `Arc::try_unwrap`,
+    /// which succeeds only if no other reference exists. `Owned` data is
+    /// moved out, an uninitialized `Lazy` runs its initializer, and
+    /// `Borrowed` data, which has no `Arc` to return, is cloned.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(value)` if the value could be obtained without cloning shared data
+    /// - `Err(arc)` with the still-shared `Arc` otherwise
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use std::sync::Arc;
+    ///
+    /// let unique = AnyCow::shared(Arc::new(vec![1, 2, 3]));
+    /// assert_eq!(unique.into_owned_or_arc(), Ok(vec![1, 2, 3]));
+    ///
+    /// let data = Arc::new(vec![1, 2, 3]);
+    /// let shared = AnyCow::shared(data.clone());
+    /// let arc = shared.into_owned_or_arc().unwrap_err();
+    /// assert!(Arc::ptr_eq(&arc, &data));
+    /// ```
+    pub fn into_owned_or_arc(self) -> Result<T, Arc<T>> {
+        match self.into_inner_arc() {
+            Ok(arc) => Arc::try_unwrap(arc),
+            Err(cow) => Ok(cow.into_owned()),
+        }
+    }
+
+    /// Returns a reference to the contained data.
+    ///
+    /// This method provides unified access to the data regardless of
+    /// the storage variant. For the `Updatable` variant, this returns
+    /// a guard that ensures the data remains valid during access.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use std::sync::Arc;
+    ///
+    /// let borrowed = AnyCow::borrowed(&"hello");
+    /// let owned = AnyCow::owned(String::from("world"));
+    /// let shared = AnyCow::shared(Arc::new(42));
+    ///
+    /// assert_eq!(*borrowed.borrow(), "hello");
+    /// assert_eq!(*owned.borrow(), "world");
+    /// assert_eq!(*shared.borrow(), 42);
+    /// ```
+    pub fn borrow(&self) -> AnyCowRef<'_, T> {
+        match self {
+            AnyCow::Borrowed(value) => AnyCowRef::Direct(value),
+            AnyCow::Owned(value) => AnyCowRef::Direct(&**value),
+            AnyCow::Shared(value) => AnyCowRef::Direct(value),
+            AnyCow::Updatable(value) => AnyCowRef::Guarded(value.load()),
+            AnyCow::Tracked(cell) => AnyCowRef::Guarded(cell.load()),
+            AnyCow::Lazy { data, init } => {
+                let value = data.get_or_init(|| ArcSwap::from_pointee(init()));
+                AnyCowRef::Guarded(value.load())
+            }
+        }
+    }
+
+    /// Returns a reference to part of the contained data.
+    ///
+    /// The returned [`MappedRef`] owns the guard (or reference) that
+    /// [`borrow()`](Self::borrow) would return, so the backing `Arc` of an
+    /// `Updatable` or `Lazy` value stays alive while the projection is in
+    /// use, even if the value is replaced meanwhile. This is the method form
+    /// of [`AnyCowRef::map()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::{AnyCow, MappedRef};
+    ///
+    /// #[derive(Clone)]
+    /// struct Config {
+    ///     name: String,
+    ///     retries: u32,
+    /// }
+    ///
+    /// fn name<'c>(config: &'c AnyCow<'_, Config>) -> MappedRef<'c, Config, str> {
+    ///     config.map_guard(|config| config.name.as_str())
+    /// }
+    ///
+    /// let config = AnyCow::updatable(Config { name: String::from("api"), retries: 3 });
+    /// let current = name(&config);
+    /// config.try_replace(Config { name: String::from("web"), retries: 1 }).unwrap();
+    ///
+    /// // The projection still points into the replaced value
+    /// assert_eq!(&*current, "api");
+    /// assert_eq!(&*name(&config), "web");
+    /// ```
+    #[doc(alias = "map_ref")]
+    pub fn map_guard<U, F>(&self, f: F) -> MappedRef<'_, T, U>
+    where
+        U: ?Sized,
+        F: FnOnce(&T) -> &U,
+    {
+        AnyCowRef::map(self.borrow(), f)
+    }
+
+    /// Returns a plain `&T` for the variants that don't need a guard.
+    ///
+    /// `Borrowed`, `Owned` and `Shared` data lives at least as long as
+    /// `&self`, so tight read loops over, say, a `Shared` dataset can skip
+    /// the [`AnyCowRef`] returned by [`borrow()`](Self::borrow) and its
+    /// match on every dereference. Returns `None` for `Updatable` and
+    /// `Lazy` variants, whose value can only be read through a guard.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use std::sync::Arc;
+    ///
+    /// let dataset = AnyCow::shared(Arc::new(vec![1u64, 2, 3]));
+    /// let values: &Vec<u64> = dataset.as_direct().unwrap();
+    /// assert_eq!(values.iter().sum::<u64>(), 6);
+    ///
+    /// assert!(AnyCow::updatable(1).as_direct().is_none());
+    /// ```
+    #[doc(alias = "as_ref")]
+    pub fn as_direct(&self) -> Option<&T> {
+        match self {
+            AnyCow::Borrowed(value) => Some(value),
+            AnyCow::Owned(value) => Some(value),
+            AnyCow::Shared(value) => Some(value),
+            AnyCow::Updatable(_) | AnyCow::Tracked(_) | AnyCow::Lazy { .. } => None,
+        }
+    }
+
+    /// Returns a reference to the contained data unless that would run a
+    /// `Lazy` initializer.
+    ///
+    /// Returns `None` for a `Lazy` that hasn't been initialized yet and
+    /// behaves like [`borrow()`](Self::borrow) otherwise, so every other
+    /// variant always returns `Some`. Status pages and metrics can show a
+    /// lazily computed value once it exists without forcing the
+    /// computation themselves.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let report = AnyCow::lazy(|| String::from("expensive report"));
+    /// let shown = report.peek().map_or_else(|| String::from("(pending)"), |r| r.clone());
+    /// assert_eq!(shown, "(pending)");
+    /// assert!(!report.is_initialized());
+    ///
+    /// let _ = report.borrow();
+    /// assert_eq!(&*report.peek().unwrap(), "expensive report");
+    /// ```
+    #[doc(alias = "try_borrow")]
+    pub fn peek(&self) -> Option<AnyCowRef<'_, T>> {
+        match self {
+            AnyCow::Lazy { data, .. } => data.get().map(|value| AnyCowRef::Guarded(value.load())),
+            AnyCow::Tracked(cell) if !cell.is_initialized() => None,
+            _ => Some(self.borrow()),
+        }
+    }
+
+    /// Returns a reference to the contained data, initializing a `Lazy`
+    /// with `f` instead of its stored initializer.
+    ///
+    /// This mirrors `OnceLock::get_or_init`: the initializer is chosen at
+    /// the borrow site, which is handy when a `Lazy` is constructed
+    /// generically and the real initializer captures local state. If the
+    /// `Lazy` is already initialized, `f` is not called. Concurrent callers
+    /// race safely and `f` runs at most once; losers wait for the winner.
+    ///
+    /// For all other variants this behaves exactly like [`borrow()`](Self::borrow).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let base = 40;
+    /// let cow: AnyCow<i32> = AnyCow::lazy(|| 0);
+    /// assert_eq!(*cow.borrow_or_init_with(|| base + 2), 42);
+    ///
+    /// // Already initialized, so the new closure is ignored
+    /// assert_eq!(*cow.borrow_or_init_with(|| 7), 42);
+    /// ```
+    pub fn borrow_or_init_with<F>(&self, f: F) -> AnyCowRef<'_, T>
+    where
+        F: FnOnce() -> T,
+    {
+        match self {
+            AnyCow::Lazy { data, .. } => {
+                let value = data.get_or_init(|| ArcSwap::from_pointee(f()));
+                AnyCowRef::Guarded(value.load())
+            }
+            AnyCow::Tracked(cell) => {
+                cell.init_with(f);
+                AnyCowRef::Guarded(cell.load())
+            }
+            _ => self.borrow(),
+        }
+    }
+
+    /// Runs `f` with a reference to the contained data and returns its result.
+    ///
+    /// The guard taken for `Updatable` and `Lazy` variants is dropped as soon
+    /// as `f` returns, which makes it impossible to accidentally hold on to
+    /// an old snapshot for longer than needed. `Lazy` variants are
+    /// initialized first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let config = AnyCow::updatable(vec![1, 2, 3]);
+    /// let total: i32 = config.with(|values| values.iter().sum());
+    /// assert_eq!(total, 6);
+    /// ```
+    pub fn with<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&T) -> R,
+    {
+        f(&self.borrow())
+    }
+
+    /// Returns `self` unless its value is empty, in which case `fallback`
+    /// is returned.
+    ///
+    /// Emptiness is defined by [`MaybeEmpty`]: `None` for `Option`
+    /// payloads, no elements for collections and strings. This expresses
+    /// "use the override if set, else the default" chains. For `Lazy`
+    /// variants this forces initialization of `self`; `fallback` is not
+    /// read. Use [`or_else()`](Self::or_else) to construct the fallback
+    /// only when it is needed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let defaults = vec![String::from("localhost")];
+    /// let overrides: Vec<String> = Vec::new();
+    ///
+    /// let hosts = AnyCow::borrowed(&overrides).or(AnyCow::borrowed(&defaults));
+    /// assert_eq!(*hosts.borrow(), defaults);
+    /// ```
+    pub fn or(self, fallback: Self) -> Self
+    where
+        T: MaybeEmpty,
+    {
+        if self.borrow().is_empty_value() {
+            fallback
+        } else {
+            self
+        }
+    }
+
+    /// Returns `self` unless its value is empty, in which case the result
+    /// of `f` is returned.
+    ///
+    /// Like [`or()`](Self::or), but `f` only runs when the fallback is
+    /// actually needed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let configured: Option<u16> = Some(8080);
+    /// let port = AnyCow::borrowed(&configured).or_else(|| AnyCow::owned(Some(80)));
+    /// assert_eq!(*port.borrow(), Some(8080));
+    ///
+    /// let port = AnyCow::owned(None).or_else(|| AnyCow::owned(Some(80)));
+    /// assert_eq!(*port.borrow(), Some(80));
+    /// ```
+    pub fn or_else<F>(self, f: F) -> Self
+    where
+        T: MaybeEmpty,
+        F: FnOnce() -> Self,
+    {
+        if self.borrow().is_empty_value() {
+            f()
+        } else {
+            self
+        }
+    }
+
+    /// Projects the contained data to one of its parts, as a `Borrowed` `AnyCow`.
+    ///
+    /// This is allocation-free and works for the variants that hand out
+    /// stable references: `Borrowed`, `Owned` and `Shared`. `Updatable` and
+    /// `Lazy` values can be replaced at any moment, so they return `None`;
+    /// use [`AnyCowRef::map()`] on a [`borrow()`](Self::borrow) instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// #[derive(Clone)]
+    /// struct Config {
+    ///     name: String,
+    /// }
+    ///
+    /// let config = AnyCow::owned(Config { name: String::from("app") });
+    /// let name = config.project(|c| &c.name).unwrap();
+    /// assert!(name.is_borrowed());
+    /// assert_eq!(*name.borrow(), "app");
+    /// ```
+    pub fn project<U, F>(&self, f: F) -> Option<AnyCow<'_, U>>
+    where
+        U: ToOwned,
+        F: FnOnce(&T) -> &U,
+    {
+        match self {
+            AnyCow::Borrowed(value) => Some(AnyCow::Borrowed(f(value))),
+            AnyCow::Owned(value) => Some(AnyCow::Borrowed(f(value))),
+            AnyCow::Shared(value) => Some(AnyCow::Borrowed(f(value))),
+            AnyCow::Updatable(_) | AnyCow::Tracked(_) | AnyCow::Lazy { .. } => None,
+        }
+    }
+
+    /// Returns a reader that memoizes the current value of an `Updatable`
+    /// or `Lazy` variant.
+    ///
+    /// The [`Cached`] reader only reloads when the value has been replaced,
+    /// which makes it well suited to extremely read-heavy loops. Returns
+    /// `None` for the other variants, whose reads are already direct.
+    /// For `Lazy` variants this forces initialization.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let config = AnyCow::updatable(10);
+    /// let mut reader = config.cached_reader().unwrap();
+    /// assert_eq!(*reader.get(), 10);
+    ///
+    /// config.try_replace(20).unwrap();
+    /// assert_eq!(*reader.get(), 20);
+    /// ```
+    pub fn cached_reader(&self) -> Option<Cached<'_, T>> {
+        self.as_updatable().map(|handle| handle.cached())
+    }
+
+    /// Attempts to atomically replace the value in an `Updatable` or `Lazy` variant.
+    ///
+    /// This method succeeds if the container is of the `Updatable` or `Lazy` variant.
+    /// The replacement is atomic and lock-free, making it perfect for
+    /// concurrent scenarios. For `Lazy` variants, this will initialize the
+    /// data if it hasn't been accessed before. A `borrow()` on another
+    /// thread that sees the new value also sees every write made before
+    /// the replacement; see [Memory ordering](AnyCow#memory-ordering).
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the replacement was successful, or was dropped by an
+    ///   [`updatable_debounced()`](Self::updatable_debounced) container
+    /// - `Err(AnyCowReplaceError)` if this container is not an `Updatable` or `Lazy` variant,
+    ///   or if the validator of an [`updatable_validated()`](Self::updatable_validated)
+    ///   container rejected the value
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let updatable = AnyCow::updatable(vec![1, 2, 3]);
+    /// assert_eq!(*updatable.borrow(), vec![1, 2, 3]);
+    ///
+    /// // Atomic replacement
+    /// assert!(updatable.try_replace(vec![4, 5, 6]).is_ok());
+    /// assert_eq!(*updatable.borrow(), vec![4, 5, 6]);
+    ///
+    /// // Also works with lazy variants
+    /// let lazy = AnyCow::lazy(|| vec![7, 8, 9]);
+    /// assert!(lazy.try_replace(vec![10, 11, 12]).is_ok());
+    /// assert_eq!(*lazy.borrow(), vec![10, 11, 12]);
+    ///
+    /// // This will fail for other variants
+    /// let owned = AnyCow::owned(vec![1, 2, 3]);
+    /// assert!(owned.try_replace(vec![4, 5, 6]).is_err());
+    /// ```
+    pub fn try_replace(&self, new_val: T) -> Result<(), AnyCowReplaceError> {
+        let handle = self.as_updatable().ok_or(AnyCowReplaceError)?;
+        handle.store(new_val).map_err(|_| AnyCowReplaceError)
+    }
+
+    /// Like [`try_replace()`](Self::try_replace), but accepts anything that
+    /// converts into `T`.
+    ///
+    /// Saves spelling out the conversion, as in `try_replace_into("debug")`
+    /// for an `AnyCow<String>`. The conversion only runs if the container
+    /// is an `Updatable` or `Lazy` variant. Prefer `try_replace()` where the
+    /// argument's type has to be inferred from `T`, e.g. for an empty
+    /// `vec![]` or an integer literal.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let level = AnyCow::updatable(String::from("info"));
+    /// level.try_replace_into("debug").unwrap();
+    /// assert_eq!(*level.borrow(), "debug");
+    ///
+    /// let owned = AnyCow::owned(String::from("info"));
+    /// assert!(owned.try_replace_into("debug").is_err());
+    /// ```
+    pub fn try_replace_into<V>(&self, new_val: V) -> Result<(), AnyCowReplaceError>
+    where
+        V: Into<T>,
+    {
+        self.as_updatable().ok_or(AnyCowReplaceError)?;
+        self.try_replace(new_val.into())
+    }
+
+    /// Computes a new value with `f` and stores it only if that succeeds.
+    ///
+    /// `f` runs only for `Updatable` and `Lazy` containers. If it returns
+    /// `Err`, nothing is stored and the current value stays in place, so a
+    /// configuration can be rebuilt from a fallible parse and swapped in
+    /// one step without a half-built value ever becoming visible.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the new value was computed and stored
+    /// - `Err(ReplaceError::Compute(e))` if `f` failed
+    /// - `Err(ReplaceError::NotReplaceable)` if this container is not an
+    ///   `Updatable` or `Lazy` variant, or if its validator rejected the
+    ///   value
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::{AnyCow, ReplaceError};
+    ///
+    /// let port = AnyCow::updatable(8080u16);
+    ///
+    /// port.try_replace_computed(|| "9090".parse()).unwrap();
+    /// assert_eq!(*port.borrow(), 9090);
+    ///
+    /// let result = port.try_replace_computed(|| "http".parse::<u16>());
+    /// assert!(matches!(result, Err(ReplaceError::Compute(_))));
+    /// assert_eq!(*port.borrow(), 9090);
+    /// ```
+    pub fn try_replace_computed<F, E>(&self, f: F) -> Result<(), ReplaceError<E>>
+    where
+        F: FnOnce() -> Result<T, E>,
+    {
+        let handle = self.as_updatable().ok_or(ReplaceError::NotReplaceable)?;
+        let new_val = f().map_err(ReplaceError::Compute)?;
+        handle
+            .store(new_val)
+            .map_err(|_| ReplaceError::NotReplaceable)
+    }
+
+    /// Replaces the value like [`try_replace()`](Self::try_replace), handing
+    /// it back together with the reason if it is rejected.
+    ///
+    /// Useful with [`updatable_validated()`](Self::updatable_validated),
+    /// where `try_replace` only reports that the value was refused.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the replacement was successful
+    /// - `Err(rejected)` with the value if the validator refused it, or if
+    ///   this container is not an `Updatable` or `Lazy` variant
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let workers = AnyCow::updatable_validated(4u32, |&n| {
+    ///     if n == 0 { Err("at least one worker is required") } else { Ok(()) }
+    /// });
+    ///
+    /// let rejected = workers.try_replace_validated(0).unwrap_err();
+    /// assert_eq!(rejected.reason().unwrap().to_string(), "at least one worker is required");
+    /// assert_eq!(rejected.into_value(), 0);
+    /// assert_eq!(*workers.borrow(), 4);
+    /// ```
+    pub fn try_replace_validated(&self, new_val: T) -> Result<(), Rejected<T>> {
+        let Some(handle) = self.as_updatable() else {
+            return Err(Rejected {
+                value: new_val,
+                reason: None,
+            });
+        };
+        handle.store(new_val)
+    }
+
+    /// Replaces the value like [`try_replace()`](Self::try_replace), reporting
+    /// whether there was an initialized value to replace.
+    ///
+    /// An uninitialized `Lazy` is initialized with `new_val` directly, so
+    /// its initializer never runs. This tells apart changing a value that
+    /// readers may already have seen, which may call for downstream
+    /// recomputation, from supplying the first value.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(WasInitialized::Yes)` if an existing value was replaced: always
+    ///   for `Updatable`, and for a `Lazy` that was already initialized
+    /// - `Ok(WasInitialized::No)` if `new_val` became the first value of a `Lazy`
+    /// - `Err(AnyCowReplaceError)` if this container is not an `Updatable` or
+    ///   `Lazy` variant, or if its validator rejected the value
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::{AnyCow, WasInitialized};
+    ///
+    /// let cow = AnyCow::lazy(|| -> Vec<u32> { unreachable!("never read") });
+    /// assert_eq!(cow.replace_status(vec![1]), Ok(WasInitialized::No));
+    /// assert_eq!(cow.replace_status(vec![2]), Ok(WasInitialized::Yes));
+    /// assert_eq!(*cow.borrow(), vec![2]);
+    /// ```
+    pub fn replace_status(&self, new_val: T) -> Result<WasInitialized, AnyCowReplaceError> {
+        match self {
+            AnyCow::Lazy { data, .. } => match data.set(ArcSwap::from_pointee(new_val)) {
+                Ok(()) => Ok(WasInitialized::No),
+                Err(value) => self
+                    .try_replace_arc(value.into_inner())
+                    .map(|()| WasInitialized::Yes)
+                    .map_err(|_| AnyCowReplaceError),
+            },
+            AnyCow::Tracked(cell) if !cell.is_initialized() => match cell.set(new_val) {
+                Ok(()) => Ok(WasInitialized::No),
+                Err(value) => self.try_replace(value).map(|()| WasInitialized::Yes),
+            },
+            _ => self.try_replace(new_val).map(|()| WasInitialized::Yes),
+        }
+    }
+
+    /// Atomically stores `new_val` only if it differs from the current value.
+    ///
+    /// Equal values are not stored at all, so watchers aren't woken and the
+    /// history doesn't grow; this deduplicates no-op reloads that would
+    /// otherwise trigger downstream recomputation. The comparison and the
+    /// store happen as one atomic step: if another writer gets in between,
+    /// the comparison is repeated against its value. For `Lazy` variants,
+    /// this will initialize the data if it hasn't been accessed before.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(true)` if the value was different and has been stored
+    /// - `Ok(false)` if the value was equal and nothing was stored
+    /// - `Err(new_val)` if this container is not an `Updatable` or `Lazy` variant,
+    ///   or if its validator rejected the value
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let config = AnyCow::updatable_with_history(String::from("v1"), 10);
+    /// assert_eq!(config.replace_if_changed(String::from("v1")), Ok(false));
+    /// assert_eq!(config.replace_if_changed(String::from("v2")), Ok(true));
+    /// assert_eq!(config.history().len(), 1);
+    /// ```
+    pub fn replace_if_changed(&self, new_val: T) -> Result<bool, T>
+    where
+        T: PartialEq,
+    {
+        let Some(handle) = self.as_updatable() else {
+            return Err(new_val);
+        };
+        let mut new_val = Arc::new(new_val);
+        loop {
+            let current = handle.load_full();
+            if *current == *new_val {
+                return Ok(false);
+            }
+            match handle.compare_and_swap_arc(&current, new_val) {
+                Ok(_) => return Ok(true),
+                Err(CasError::Changed(offered)) => new_val = offered,
+                Err(CasError::Rejected(rejected)) => {
+                    return Err(unwrap_or_to_owned(rejected.into_value()))
+                }
+            }
+        }
+    }
+
+    /// Atomically stores `candidate` if it is greater than the current value.
+    ///
+    /// The `Ord` counterpart to `AtomicUsize::fetch_max`, for monotonic
+    /// values such as high-water marks. The comparison and store retry
+    /// until they succeed against an unchanged value, so concurrent callers
+    /// never lose a larger candidate. Nothing is stored when the current
+    /// value is already at least as large, so watchers and history don't see
+    /// no-op updates.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(value)` with the value in place afterwards: `candidate` if it
+    ///   was stored, the current value otherwise
+    /// - `Err(AnyCowReplaceError)` if this container is not an `Updatable` or `Lazy` variant,
+    ///   or if its validator rejected `candidate`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let peak_connections = AnyCow::updatable(10);
+    /// assert_eq!(*peak_connections.fetch_max(25).unwrap(), 25);
+    /// assert_eq!(*peak_connections.fetch_max(12).unwrap(), 25);
+    /// ```
+    pub fn fetch_max(&self, candidate: T) -> Result<Arc<T>, AnyCowReplaceError>
+    where
+        T: Ord,
+    {
+        self.store_if_ordered(candidate, std::cmp::Ordering::Greater)
+    }
+
+    /// Atomically stores `candidate` if it is less than the current value.
+    ///
+    /// Works like [`fetch_max()`](Self::fetch_max) with the comparison
+    /// reversed, for low-water marks such as the best latency seen.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(value)` with the value in place afterwards
+    /// - `Err(AnyCowReplaceError)` if this container is not an `Updatable` or `Lazy` variant,
+    ///   or if its validator rejected `candidate`
+    pub fn fetch_min(&self, candidate: T) -> Result<Arc<T>, AnyCowReplaceError>
+    where
+        T: Ord,
+    {
+        self.store_if_ordered(candidate, std::cmp::Ordering::Less)
+    }
+
+    /// Stores `candidate` while it compares as `wins` against the current value.
+    fn store_if_ordered(
+        &self,
+        candidate: T,
+        wins: std::cmp::Ordering,
+    ) -> Result<Arc<T>, AnyCowReplaceError>
+    where
+        T: Ord,
+    {
+        let handle = self.as_updatable().ok_or(AnyCowReplaceError)?;
+        let mut candidate = Arc::new(candidate);
+        loop {
+            let current = handle.load_full();
+            if candidate.as_ref().cmp(&current) != wins {
+                return Ok(current);
+            }
+            match handle.compare_and_swap_arc(&current, candidate.clone()) {
+                Ok(_) => return Ok(candidate),
+                Err(CasError::Changed(offered)) => candidate = offered,
+                Err(CasError::Rejected(_)) => return Err(AnyCowReplaceError),
+            }
+        }
+    }
+
+    /// Atomically resets an `Updatable` or `Lazy` value to `T::default()`.
+    ///
+    /// Equivalent to `try_replace(T::default())`, but states the intent at
+    /// the call site. Useful for emptying shared caches. For `Lazy`
+    /// variants, this will initialize the data if it hasn't been accessed
+    /// before.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the value was reset
+    /// - `Err(AnyCowReplaceError)` if this container is not an `Updatable` or `Lazy` variant,
+    ///   or if its validator rejected `T::default()`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let cache = AnyCow::updatable(vec![1, 2, 3]);
+    /// cache.clear().unwrap();
+    /// assert!(cache.borrow().is_empty());
+    ///
+    /// let owned = AnyCow::owned(vec![1, 2, 3]);
+    /// assert!(owned.clear().is_err());
+    /// ```
+    pub fn clear(&self) -> Result<(), AnyCowReplaceError>
+    where
+        T: Default,
+    {
+        self.try_replace(T::default())
+    }
+
+    /// Atomically removes the elements of an `Updatable` or `Lazy` collection
+    /// that don't match a predicate.
+    ///
+    /// The predicate takes the same arguments as the collection's own
+    /// `retain` (see [`Retainable`]). The collection is cloned, filtered and
+    /// stored back in a single atomic step, so readers see either the old
+    /// or the filtered collection. If a
+    /// concurrent writer gets in first, the filter is re-applied to the new
+    /// value, which means `f` may see some elements more than once.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the collection was filtered
+    /// - `Err(AnyCowReplaceError)` if this container is not an `Updatable` or `Lazy` variant,
+    ///   or if its validator rejected the filtered collection
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use std::collections::HashMap;
+    ///
+    /// let cache = AnyCow::updatable(HashMap::from([("fresh", 1), ("stale", 9)]));
+    /// cache.retain(|_, age| *age < 5).unwrap();
+    /// assert!(!cache.borrow().contains_key("stale"));
+    /// ```
+    pub fn retain<F>(&self, mut f: F) -> Result<(), AnyCowReplaceError>
+    where
+        T: Retainable<F>,
+    {
+        let handle = self.as_updatable().ok_or(AnyCowReplaceError)?;
+        handle
+            .rcu(|current| {
+                let mut value = T::to_owned(current);
+                value.retain_items(&mut f);
+                value
+            })
+            .map(drop)
+            .map_err(|_| AnyCowReplaceError)
+    }
+
+    /// Returns a handle grouping the atomic operations of an `Updatable`,
+    /// `Tracked` or `Lazy` value.
+    ///
+    /// The variant is checked once here; the returned [`UpdatableHandle`]
+    /// exposes `load`, `store`, `swap`, `compare_and_swap` and `rcu` without
+    /// any further variant checks; writes only fail if the validator of an
+    /// [`updatable_validated()`](Self::updatable_validated) container
+    /// refuses them. Returns `None` for the other variants.
+    /// For `Lazy` variants this forces initialization.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let counter = AnyCow::updatable(0);
+    /// let handle = counter.as_updatable().unwrap();
+    ///
+    /// handle.rcu(|n| n + 1).unwrap();
+    /// let previous = handle.swap(10).unwrap();
+    /// assert_eq!(*previous, 1);
+    ///
+    /// let current = handle.load_full();
+    /// assert!(handle.compare_and_swap(&current, 11).is_ok());
+    /// assert!(handle.compare_and_swap(&current, 12).is_err());
+    /// assert_eq!(*counter.borrow(), 11);
+    ///
+    /// assert!(AnyCow::owned(0).as_updatable().is_none());
+    /// ```
+    pub fn as_updatable(&self) -> Option<UpdatableHandle<'_, T>> {
+        match self {
+            AnyCow::Updatable(value) => Some(UpdatableHandle::plain(value)),
+            AnyCow::Tracked(cell) => Some(UpdatableHandle::new(cell)),
+            AnyCow::Lazy { data, init } => Some(UpdatableHandle::plain(
+                data.get_or_init(|| ArcSwap::from_pointee(init())),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Returns a future that resolves when an `Updatable`, `Tracked` or
+    /// `Lazy` value is next replaced.
+    ///
+    /// The async counterpart to [`watch()`](Self::watch): the future
+    /// completes with the latest value once any replacement has happened
+    /// after this call, without blocking a thread. Several replacements
+    /// before the task is polled again are coalesced into one wakeup. The
+    /// future is cancel-safe, dropping it (e.g. in `tokio::select!`) loses
+    /// nothing, and a new call starts waiting for the next replacement.
+    /// Returns `None` for the other variants. For `Lazy` variants this
+    /// forces initialization.
+    ///
+    /// Only writes made through `AnyCow` (or its [`UpdatableHandle`]) wake
+    /// the future; a store made directly on the `ArcSwap` of an `Updatable`
+    /// isn't noticed until the next one that is. Plain `Updatable` and
+    /// `Lazy` values share one process-wide wakeup, so a pending future
+    /// may be polled for writes to other containers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let config = AnyCow::updatable(String::from("v1"));
+    ///
+    /// let changed = config.changed().unwrap();
+    /// config.try_replace(String::from("v2")).unwrap();
+    /// config.try_replace(String::from("v3")).unwrap();
+    /// assert_eq!(*changed.await, "v3");
+    /// # });
+    /// ```
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+    pub fn changed(&self) -> Option<impl std::future::Future<Output = Arc<T>> + '_> {
+        self.as_updatable().map(|handle| handle.changed())
+    }
+
+    /// Returns a blocking iterator over replacements of an `Updatable`,
+    /// `Tracked` or `Lazy` value.
+    ///
+    /// Every [`next()`](Iterator::next) call on the returned [`Watcher`]
+    /// waits until the value changes and then yields the latest snapshot,
+    /// which turns polling loops in watcher threads into a plain `for` loop.
+    /// Updates that happen between two calls are coalesced. Returns `None`
+    /// for the other variants. For `Lazy` variants this forces
+    /// initialization.
+    ///
+    /// As with [`changed()`](Self::changed), only writes made through
+    /// `AnyCow` wake the watcher, and watchers of plain `Updatable` and
+    /// `Lazy` values share one process-wide wakeup.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use std::thread;
+    ///
+    /// let config = AnyCow::updatable(String::from("v1"));
+    ///
+    /// thread::scope(|s| {
+    ///     let mut watcher = config.watch().unwrap();
+    ///     s.spawn(|| config.try_replace(String::from("v2")).unwrap());
+    ///
+    ///     let snapshot = watcher.next().unwrap();
+    ///     assert_eq!(*snapshot, "v2");
+    /// });
+    /// ```
+    pub fn watch(&self) -> Option<Watcher<'_, T>> {
+        self.as_updatable().map(|handle| handle.watch())
+    }
+
+    /// Captures a token identifying the current value of an `Updatable` or `Lazy`.
+    ///
+    /// Pass the token to [`replace_if()`](Self::replace_if) to store a new
+    /// value only if nothing was stored in between. Returns `None` for the
+    /// other variants. For `Lazy` variants this forces initialization.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let cow = AnyCow::updatable(1);
+    /// let token = cow.snapshot_token().unwrap();
+    /// assert_eq!(*token.value(), 1);
+    /// ```
+    pub fn snapshot_token(&self) -> Option<Token<T>> {
+        self.as_updatable().map(|handle| Token(handle.load_full()))
+    }
+
+    /// Atomically stores `new_val` only if the value hasn't been replaced
+    /// since `token` was taken.
+    ///
+    /// This gives optimistic concurrency without requiring `T: PartialEq`:
+    /// tokens compare by identity, and because a token keeps its snapshot
+    /// alive, a replaced value can never be mistaken for the current one.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the value was replaced
+    /// - `Err(new_val)` if another write happened after the token was taken,
+    ///   if this container is not an `Updatable` or `Lazy` variant, or if its
+    ///   validator rejected the value
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let cow = AnyCow::updatable(vec![1]);
+    /// let token = cow.snapshot_token().unwrap();
+    ///
+    /// // Someone else writes first
+    /// cow.try_replace(vec![2]).unwrap();
+    ///
+    /// // Our update is rejected and handed back
+    /// assert_eq!(cow.replace_if(token, vec![3]), Err(vec![3]));
+    ///
+    /// let token = cow.snapshot_token().unwrap();
+    /// assert!(cow.replace_if(token, vec![3]).is_ok());
+    /// assert_eq!(*cow.borrow(), vec![3]);
+    /// ```
+    pub fn replace_if(&self, token: Token<T>, new_val: T) -> Result<(), T> {
+        let Some(handle) = self.as_updatable() else {
+            return Err(new_val);
+        };
+        handle
+            .compare_and_swap(&token.0, new_val)
+            .map(drop)
+            .map_err(|error| unwrap_or_to_owned(error.into_value()))
+    }
+
+    /// Starts a transactional edit of an `Updatable` or `Lazy` value.
+    ///
+    /// The returned [`Editor`] holds a private copy of the current value that
+    /// can be mutated any number of times through `DerefMut`. Nothing is
+    /// visible to readers until [`Editor::commit()`] stores the result in a
+    /// single atomic step, so a batch of changes costs one allocation instead
+    /// of one per change. Returns `None` for the other variants. For `Lazy`
+    /// variants this forces initialization.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let cow = AnyCow::updatable(vec![1, 2, 3]);
+    ///
+    /// let mut editor = cow.edit().unwrap();
+    /// editor.push(4);
+    /// editor.retain(|x| x % 2 == 0);
+    /// editor.commit().unwrap();
+    ///
+    /// assert_eq!(*cow.borrow(), vec![2, 4]);
+    /// ```
+    pub fn edit(&self) -> Option<Editor<'_, T>> {
+        self.as_updatable().map(|handle| {
+            let base = handle.load_full();
+            Editor {
+                handle,
+                value: base.as_ref().to_owned(),
+                base,
+            }
+        })
+    }
+
+    /// Temporarily replaces the value of an `Updatable` or `Lazy`, restoring
+    /// the previous value when the returned [`ScopeGuard`] is dropped.
+    ///
+    /// `temp` is stored immediately. Unless [`ScopeGuard::commit()`] is
+    /// called, dropping the guard atomically stores the previous value
+    /// back, also when unwinding from a panic. This is meant for tests
+    /// that override global configuration. Returns `None` for the other
+    /// variants, or if the validator rejects `temp`. For `Lazy` variants
+    /// this forces initialization.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// static LOG_LEVEL: AnyCow<'static, String> = AnyCow::lazy(|| String::from("info"));
+    ///
+    /// {
+    ///     let _guard = LOG_LEVEL.scoped_replace(String::from("trace")).unwrap();
+    ///     assert_eq!(*LOG_LEVEL.borrow(), "trace");
+    /// }
+    /// assert_eq!(*LOG_LEVEL.borrow(), "info");
+    ///
+    /// LOG_LEVEL.scoped_replace(String::from("debug")).unwrap().commit();
+    /// assert_eq!(*LOG_LEVEL.borrow(), "debug");
+    /// ```
+    pub fn scoped_replace(&self, temp: T) -> Option<ScopeGuard<'_, T>> {
+        let handle = self.as_updatable()?;
+        let previous = handle.swap(temp).ok()?;
+        Some(ScopeGuard {
+            handle,
+            previous: Some(previous),
+        })
+    }
+
+    /// Replaces the value like [`try_replace()`](Self::try_replace) and
+    /// reports whether other writers got in between.
+    ///
+    /// The returned [`ReplaceStats`] carries the generation read just
+    /// before the store and the one the store produced. Sampling
+    /// [`ReplaceStats::was_contended()`] in production shows whether a
+    /// value assumed to be update-light actually sees concurrent writes.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(stats)` if the replacement was successful
+    /// - `Err(AnyCowReplaceError)` if this container is not a `Tracked` variant,
+    ///   or if its validator rejected the value
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let config = AnyCow::updatable_tracked(1);
+    /// let stats = config.try_replace_tracked(2).unwrap();
+    /// assert_eq!(stats.generation, stats.observed_generation + 1);
+    /// assert!(!stats.was_contended());
+    /// ```
+    pub fn try_replace_tracked(&self, new_val: T) -> Result<ReplaceStats, AnyCowReplaceError> {
+        match self {
+            AnyCow::Tracked(cell) => cell.store_tracked(new_val).map_err(|_| AnyCowReplaceError),
+            _ => Err(AnyCowReplaceError),
+        }
+    }
+
+    /// Attempts to atomically replace the value with an existing `Arc<T>`.
+    ///
+    /// Works like [`try_replace()`](Self::try_replace), but stores the given
+    /// `Arc` directly instead of wrapping a new value. This allows handing a
+    /// snapshot from one container to many others without cloning `T`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the replacement was successful
+    /// - `Err(new_val)` if this container is not an `Updatable` or `Lazy` variant,
+    ///   or if its validator rejected the value
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use std::sync::Arc;
+    ///
+    /// let source = AnyCow::updatable(vec![1, 2, 3]);
+    /// let mirror = AnyCow::updatable(Vec::new());
+    ///
+    /// let snapshot = source.to_arc();
+    /// mirror.try_replace_arc(snapshot.clone()).unwrap();
+    /// assert!(Arc::ptr_eq(&snapshot, &mirror.to_arc()));
+    /// ```
+    pub fn try_replace_arc(&self, new_val: Arc<T>) -> Result<(), Arc<T>> {
+        let Some(handle) = self.as_updatable() else {
+            return Err(new_val);
+        };
+        handle.store_arc(new_val).map_err(Rejected::into_value)
+    }
+
+    /// Replaces the value with the current value of `source`.
+    ///
+    /// `source` is snapshotted with [`to_arc()`](Self::to_arc) and the
+    /// snapshot stored as with [`try_replace_arc()`](Self::try_replace_arc),
+    /// so when `source` is `Shared`, `Updatable` or `Lazy` both containers
+    /// end up sharing one `Arc` and nothing is cloned. `Borrowed` and
+    /// `Owned` sources are cloned once. This mirrors one container into
+    /// another, such as promoting a staging configuration to live.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the replacement was successful
+    /// - `Err(AnyCowReplaceError)` if this container is not an `Updatable` or
+    ///   `Lazy` variant, or if its validator rejected the value
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use std::sync::Arc;
+    ///
+    /// let staging = AnyCow::updatable(String::from("v2"));
+    /// let live = AnyCow::updatable(String::from("v1"));
+    ///
+    /// live.try_replace_from(&staging).unwrap();
+    /// assert!(Arc::ptr_eq(&live.to_arc(), &staging.to_arc()));
+    /// ```
+    pub fn try_replace_from(&self, source: &AnyCow<'_, T>) -> Result<(), AnyCowReplaceError> {
+        if !self.is_guarded_read() {
+            // Don't bother snapshotting a source that can't be stored.
+            return Err(AnyCowReplaceError);
+        }
+        self.try_replace_arc(source.to_arc())
+            .map_err(|_| AnyCowReplaceError)
+    }
+
+    /// Atomically shrinks the capacity of an `Updatable` or `Lazy` collection.
+    ///
+    /// The current value is cloned, compacted and stored back in a single
+    /// atomic step, retrying if a concurrent writer replaces the value in the
+    /// meantime. Useful for long-running services whose shared collections
+    /// grow and later shrink.
+    ///
+    /// For `Vec`, `String` and similar types the clone is what releases the
+    /// spare capacity, since cloning allocates only for the elements, and the
+    /// [`Compact`] call is then cheap. It matters for types such as `HashMap`
+    /// whose clones keep the original capacity.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the value was compacted
+    /// - `Err(AnyCowReplaceError)` if this container is not an `Updatable` or `Lazy` variant,
+    ///   or if its validator rejected the compacted value
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let mut items = Vec::with_capacity(1024);
+    /// items.extend([1, 2, 3]);
+    /// let cow = AnyCow::updatable(items);
+    ///
+    /// cow.compact().unwrap();
+    /// assert_eq!(cow.borrow().capacity(), 3);
+    /// ```
+    pub fn compact(&self) -> Result<(), AnyCowReplaceError>
+    where
+        T: Compact,
+    {
+        let handle = self.as_updatable().ok_or(AnyCowReplaceError)?;
+        handle
+            .rcu(|current| {
+                let mut value = T::to_owned(current);
+                value.compact();
+                value
+            })
+            .map(drop)
+            .map_err(|_| AnyCowReplaceError)
+    }
+
+    /// Estimates the heap memory held by this container, in bytes.
+    ///
+    /// Counts the `Box` or `Arc` allocation around the value plus the heap
+    /// memory the value reports through [`HeapSize`]. `Borrowed` data isn't
+    /// owned and counts as 0, as does an uninitialized `Lazy`. For `Shared`,
+    /// `Updatable` and `Lazy` variants the whole allocation is counted even
+    /// if other handles share it; snapshots kept alive elsewhere, including
+    /// the [`history()`](Self::history), aren't counted. A `Tracked`
+    /// container also counts its boxed [`UpdatableCell`] and the boxes of
+    /// any policies it was built with, such as the history buffer or a
+    /// validator.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use std::mem::size_of;
+    ///
+    /// let bytes = Vec::<u8>::with_capacity(1024);
+    /// assert_eq!(AnyCow::borrowed(&bytes).heap_size(), 0);
+    ///
+    /// let owned = AnyCow::owned(bytes.clone());
+    /// assert_eq!(owned.heap_size(), size_of::<Vec<u8>>() + owned.borrow().capacity());
+    /// ```
+    pub fn heap_size(&self) -> usize
+    where
+        T: HeapSize,
+    {
+        // An `ArcInner` stores the strong and weak counts before the value.
+        const ARC_HEADER: usize = 2 * std::mem::size_of::<usize>();
+
+        match self {
+            AnyCow::Borrowed(_) => 0,
+            AnyCow::Owned(value) => std::mem::size_of::<T>() + value.heap_size(),
+            AnyCow::Shared(value) => ARC_HEADER + std::mem::size_of::<T>() + value.heap_size(),
+            AnyCow::Updatable(value) => {
+                ARC_HEADER + std::mem::size_of::<T>() + value.load().heap_size()
+            }
+            AnyCow::Tracked(cell) => {
+                let value = if cell.is_initialized() {
+                    ARC_HEADER + std::mem::size_of::<T>() + cell.load().heap_size()
+                } else {
+                    0
+                };
+                std::mem::size_of::<UpdatableCell<T>>() + cell.overhead() + value
+            }
+            AnyCow::Lazy { data, .. } => data.get().map_or(0, |value| {
+                ARC_HEADER + std::mem::size_of::<T>() + value.load().heap_size()
+            }),
+        }
+    }
+
+    /// Returns the previous values retained by a `Tracked` container created
+    /// with [`updatable_with_history()`](Self::updatable_with_history), oldest
+    /// first.
+    ///
+    /// The current value is not included. Returns an empty `Vec` for every
+    /// other container.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let config = AnyCow::updatable_with_history(String::from("v1"), 5);
+    /// config.try_replace(String::from("v2")).unwrap();
+    /// assert_eq!(*config.history()[0], "v1");
+    ///
+    /// let plain = AnyCow::updatable(String::from("v1"));
+    /// plain.try_replace(String::from("v2")).unwrap();
+    /// assert!(plain.history().is_empty());
+    /// ```
+    pub fn history(&self) -> Vec<Arc<T>> {
+        match self {
+            AnyCow::Tracked(cell) => cell.history(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Folds over the values retained by a `Tracked` container created
+    /// with [`updatable_with_history()`](Self::updatable_with_history),
+    /// oldest first.
+    ///
+    /// Handy for a moving average or a trend over the last few values. The
+    /// current value is not included, and every other container returns
+    /// `init` unchanged.
+    ///
+    /// The history is locked while `f` runs, so no snapshot is cloned or
+    /// collected into a `Vec`: replacements of this container wait until
+    /// the fold is done, and `f` must not write to this container, which
+    /// deadlocks.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let latency = AnyCow::updatable_with_history(10, 3);
+    /// for ms in [20, 30, 40] {
+    ///     latency.try_replace(ms).unwrap();
+    /// }
+    ///
+    /// // The ring keeps the three values before the current one
+    /// let (sum, count) = latency.fold_history((0, 0), |(sum, count), ms| (sum + ms, count + 1));
+    /// assert_eq!(sum / count, 20);
+    /// ```
+    pub fn fold_history<B, F>(&self, init: B, f: F) -> B
+    where
+        F: FnMut(B, &T) -> B,
+    {
+        match self {
+            AnyCow::Tracked(cell) => cell.fold_history(init, f),
+            _ => init,
+        }
+    }
+
+    /// Changes the maximum age of a container created by
+    /// [`updatable_with_refresh()`](Self::updatable_with_refresh).
+    ///
+    /// Takes effect on the next read: the value's age is still measured
+    /// from the last reload, so shortening `ttl` can make the current
+    /// value stale right away. This also re-enables refreshing after
+    /// [`clear_refresh()`](Self::clear_refresh).
+    ///
+    /// Returns `false`, changing nothing, for containers without a refresh
+    /// function.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use std::time::Duration;
+    ///
+    /// let config = AnyCow::updatable_with_refresh(0, Duration::from_secs(3600), || 1);
+    /// assert_eq!(*config.borrow(), 0);
+    ///
+    /// // Incident: pick up changes on every read
+    /// assert!(config.set_refresh(Duration::ZERO));
+    /// assert_eq!(*config.borrow(), 1);
+    ///
+    /// assert!(!AnyCow::updatable(0).set_refresh(Duration::ZERO));
+    /// ```
+    pub fn set_refresh(&self, ttl: Duration) -> bool {
+        match self {
+            AnyCow::Tracked(cell) => cell.set_refresh(ttl),
+            _ => false,
+        }
+    }
+
+    /// Stops a container created by
+    /// [`updatable_with_refresh()`](Self::updatable_with_refresh) from
+    /// reloading its value, for example during maintenance of the source.
+    ///
+    /// The current value is served until it's replaced by other means or
+    /// [`set_refresh()`](Self::set_refresh) enables refreshing again.
+    /// Returns `false` for containers without a refresh function.
+    pub fn clear_refresh(&self) -> bool {
+        self.set_refresh(Duration::MAX)
+    }
+
+    /// Returns an owning snapshot of the current value.
+    ///
+    /// The [`Snapshot`] has the same type and lifetime semantics for every
+    /// variant: `Shared`, `Updatable` and `Lazy` share their `Arc`, while
+    /// `Borrowed` and `Owned` data is cloned into a new one. Generic code
+    /// can keep it beyond the scope of a [`borrow()`](Self::borrow), even
+    /// after the container itself is gone. Replacing an `Updatable` value
+    /// doesn't affect snapshots taken earlier.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::{AnyCow, Snapshot};
+    ///
+    /// fn keep<T>(cow: &AnyCow<'_, T>, readers: &mut Vec<Snapshot<T>>)
+    /// where
+    ///     T: Clone,
+    /// {
+    ///     readers.push(cow.snapshot());
+    /// }
+    ///
+    /// let mut readers = Vec::new();
+    /// let config = AnyCow::updatable(1);
+    /// keep(&config, &mut readers);
+    /// config.try_replace(2).unwrap();
+    /// keep(&AnyCow::owned(3), &mut readers);
+    ///
+    /// let values: Vec<i32> = readers.iter().map(|snapshot| **snapshot).collect();
+    /// assert_eq!(values, vec![1, 3]);
+    /// ```
+    pub fn snapshot(&self) -> Snapshot<T> {
+        Snapshot(self.to_arc())
+    }
+
+    /// Converts this `AnyCow` to an `Arc<T>`.
+    ///
+    /// This method will clone the data if necessary to create an `Arc`.
+    /// If the container already holds an `Arc` (in the `Shared` or `Updatable`
+    /// variants), it may reuse the existing `Arc`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use std::sync::Arc;
+    ///
+    /// let cow = AnyCow::owned(String::from("hello"));
+    /// let arc: Arc<String> = cow.to_arc();
+    /// assert_eq!(*arc, "hello");
+    /// ```
+    pub fn to_arc(&self) -> Arc<T> {
+        match self {
+            AnyCow::Borrowed(value) => Arc::new((*value).to_owned()),
+            AnyCow::Owned(value) => Arc::new((**value).to_owned()),
+            AnyCow::Shared(value) => value.clone(),
+            AnyCow::Updatable(value) => value.load_full(),
+            AnyCow::Tracked(cell) => cell.load_full(),
+            AnyCow::Lazy { data, init } => data
+                .get_or_init(|| ArcSwap::from_pointee(init()))
+                .load_full(),
+        }
+    }
+
+    /// Converts this `AnyCow` to a shared variant.
+    ///
+    /// Borrowed stays borrowed (no heap allocation).
+    /// All other variants allocate or clone into an Arc<T>.
+    /// Calling to_shared on a Lazy will force initialization.
+    ///
+    /// Because `Borrowed` stays borrowed, the result keeps the `'a` lifetime
+    /// and can't be moved into another thread or stored as `'static`. Use
+    /// [`into_shared()`](Self::into_shared) when you need a genuinely
+    /// `'static`, `Arc`-backed value; it clones borrowed data instead.
+    ///
+    /// # Conversion behavior:
+    /// - `Borrowed` → stays `Borrowed` (zero-cost)
+    /// - `Owned` → converts to `Shared` with `Arc`
+    /// - `Shared` → returns a clone (cheap `Arc` clone)
+    /// - `Updatable` → converts to `Shared` with current value
+    /// - `Lazy` → initializes if needed and converts to `Shared`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use std::sync::Arc;
+    ///
+    /// let data = "hello";
+    /// let borrowed = AnyCow::borrowed(&data);
+    /// let shared = borrowed.to_shared();
+    /// assert!(shared.is_borrowed()); // Still borrowed!
+    ///
+    /// let owned = AnyCow::owned(String::from("world"));
+    /// let shared = owned.to_shared();
+    /// assert!(shared.is_shared()); // Now shared
+    /// ```
+    pub fn to_shared(&self) -> AnyCow<'a, T> {
+        match self {
+            AnyCow::Borrowed(value) => AnyCow::Borrowed(value),
+            _ => AnyCow::Shared(self.to_arc()),
+        }
+    }
+
+    /// Detaches this `AnyCow` from the lifetime `'a`.
+    ///
+    /// Only a `Borrowed` variant depends on `'a`; it is cloned into an
+    /// `Owned` one. Every other variant is moved into the result
+    /// unchanged, so a `Shared` keeps sharing its `Arc` and an `Updatable`
+    /// or `Lazy` keeps its atomic storage, unlike
+    /// [`into_owned()`](Self::into_owned) or
+    /// [`into_shared()`](Self::into_shared). Use it to store an `AnyCow`
+    /// received with a short lifetime in a longer-lived struct.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// struct Registry {
+    ///     names: Vec<AnyCow<'static, String>>,
+    /// }
+    ///
+    /// let mut registry = Registry { names: Vec::new() };
+    /// {
+    ///     let local = String::from("temporary");
+    ///     registry.names.push(AnyCow::borrowed(&local).into_static());
+    /// }
+    /// registry.names.push(AnyCow::updatable(String::from("live")).into_static());
+    ///
+    /// assert!(registry.names[0].is_owned());
+    /// assert!(registry.names[1].is_updatable());
+    /// ```
+    #[doc(alias = "into_owned_anycow")]
+    pub fn into_static(self) -> AnyCow<'static, T>
+    where
+        T: 'static,
+    {
+        match self {
+            AnyCow::Borrowed(value) => AnyCow::Owned(Box::new(value.to_owned())),
+            AnyCow::Owned(value) => AnyCow::Owned(value),
+            AnyCow::Shared(value) => AnyCow::Shared(value),
+            AnyCow::Updatable(value) => AnyCow::Updatable(value),
+            AnyCow::Tracked(cell) => AnyCow::Tracked(cell),
+            AnyCow::Lazy { data, init } => AnyCow::Lazy { data, init },
+        }
+    }
+
+    /// Consumes this `AnyCow` and converts it into a `'static` shared variant.
+    ///
+    /// Unlike [`to_shared()`](Self::to_shared), this always produces an
+    /// `AnyCow::Shared`, reusing existing allocations wherever possible.
+    /// The result can be sent to other threads whenever `T: Send + Sync`.
+    ///
+    /// # Conversion behavior:
+    /// - `Borrowed` → clones the data into a new `Arc`
+    /// - `Owned` → moves the boxed value into a new `Arc` (no clone)
+    /// - `Shared` → returned as-is
+    /// - `Updatable` → reuses the current `Arc` snapshot (no clone)
+    /// - `Lazy` → initializes if needed and reuses the `Arc` snapshot (no clone)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let owned = AnyCow::owned(String::from("hello"));
+    /// let shared = owned.into_shared();
+    /// assert!(shared.is_shared());
+    ///
+    /// let data = String::from("world");
+    /// let shared = AnyCow::borrowed(&data).into_shared();
+    /// assert!(shared.is_shared()); // Borrowed data is cloned
+    /// assert_eq!(*shared.borrow(), "world");
+    /// ```
+    #[doc(alias = "promote_to_shared")]
+    pub fn into_shared(self) -> AnyCow<'static, T>
+    where
+        T: 'static,
+    {
+        AnyCow::Shared(self.into_arc())
+    }
+
+    /// Converts this `AnyCow` into an `Arc`, reusing an existing one.
+    fn into_arc(self) -> Arc<T> {
+        match self {
+            AnyCow::Borrowed(value) => Arc::new(value.to_owned()),
+            AnyCow::Owned(value) => Arc::from(value),
+            AnyCow::Shared(value) => value,
+            AnyCow::Updatable(value) => value.into_inner(),
+            AnyCow::Tracked(cell) => cell.into_inner(),
+            AnyCow::Lazy { data, init } => match data.into_inner() {
+                Some(value) => value.into_inner(),
+                None => Arc::new(init()),
+            },
+        }
+    }
+
+    /// Converts this `AnyCow` into a `Shared` one whose `Arc` comes from
+    /// `pool` if an equal value is already there.
+    ///
+    /// If `pool` holds a value equal to this one, the result shares that
+    /// `Arc` and this container's data is dropped. Otherwise the data is
+    /// turned into an `Arc` as by [`into_shared()`](Self::into_shared),
+    /// cloning only `Borrowed` data, and added to the pool. Interning many
+    /// containers with equal values, such as repeated strings or
+    /// configurations, leaves one allocation per distinct value. The pool
+    /// belongs to the caller, who decides when to clear it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use std::collections::HashSet;
+    /// use std::sync::Arc;
+    ///
+    /// let mut pool = HashSet::new();
+    /// let a = AnyCow::owned(String::from("us-east-1")).intern(&mut pool);
+    /// let b = AnyCow::updatable(String::from("us-east-1")).intern(&mut pool);
+    ///
+    /// assert!(b.is_shared());
+    /// assert!(Arc::ptr_eq(&a.to_arc(), &b.to_arc()));
+    /// assert_eq!(pool.len(), 1);
+    /// ```
+    pub fn intern<S>(self, pool: &mut HashSet<Arc<T>, S>) -> Self
+    where
+        T: Eq + std::hash::Hash,
+        S: std::hash::BuildHasher,
+    {
+        if let Some(existing) = pool.get(&*self.borrow()) {
+            return AnyCow::Shared(existing.clone());
+        }
+        let value = self.into_arc();
+        pool.insert(value.clone());
+        AnyCow::Shared(value)
+    }
+
+    /// Consumes this `AnyCow` and converts it into a `'static` `Updatable` variant.
+    ///
+    /// Existing allocations are reused wherever possible.
+    ///
+    /// # Conversion behavior:
+    /// - `Borrowed` → clones the data into a new `Updatable`
+    /// - `Owned` → moves the boxed value into a new `Arc` (no clone)
+    /// - `Shared` → adopts the existing `Arc` (no clone)
+    /// - `Updatable` and `Tracked` → returned as-is
+    /// - `Lazy` → moves the already-initialized storage over (no clone),
+    ///   or initializes it first
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let lazy = AnyCow::lazy(|| vec![1, 2, 3]);
+    /// lazy.borrow(); // initialize
+    ///
+    /// let updatable = lazy.into_updatable();
+    /// assert!(updatable.is_updatable());
+    /// updatable.try_replace(vec![4]).unwrap();
+    /// ```
+    pub fn into_updatable(self) -> AnyCow<'static, T>
+    where
+        T: 'static,
+    {
+        let value = match self {
+            AnyCow::Borrowed(value) => ArcSwap::from_pointee(value.to_owned()),
+            AnyCow::Owned(value) => ArcSwap::from(Arc::from(value)),
+            AnyCow::Shared(value) => ArcSwap::from(value),
+            AnyCow::Updatable(value) => value,
+            AnyCow::Tracked(cell) => return AnyCow::Tracked(cell),
+            AnyCow::Lazy { data, init } => data
+                .into_inner()
+                .unwrap_or_else(|| ArcSwap::from_pointee(init())),
+        };
+        AnyCow::Updatable(value)
+    }
+
+    /// Freezes an `Updatable` or `Lazy` in place into a `Shared` snapshot of
+    /// its current value.
+    ///
+    /// A frozen value can no longer change, which makes it safe to use as
+    /// a hash map key or to hand to code that assumes immutability. Use
+    /// [`thaw()`](Self::thaw) to make it updatable again. The payload is
+    /// never cloned; a `Lazy` is initialized first.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the container is now `Shared` (freezing a `Shared` is a no-op)
+    /// - `Err(AnyCowReplaceError)` for `Borrowed` and `Owned` variants, which are left unchanged
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let mut rules = AnyCow::updatable(vec![String::from("allow")]);
+    /// rules.try_replace(vec![String::from("deny")]).unwrap();
+    ///
+    /// rules.freeze().unwrap();
+    /// assert!(rules.is_shared());
+    /// assert!(rules.try_replace(Vec::new()).is_err());
+    ///
+    /// rules.thaw().unwrap();
+    /// assert!(rules.try_replace(Vec::new()).is_ok());
+    /// ```
+    pub fn freeze(&mut self) -> Result<(), AnyCowReplaceError> {
+        if self.is_shared() {
+            return Ok(());
+        }
+        let snapshot = self.as_updatable().ok_or(AnyCowReplaceError)?.load_full();
+        *self = AnyCow::Shared(snapshot);
+        Ok(())
+    }
+
+    /// Thaws a `Shared` in place into an `Updatable` holding the same `Arc`.
+    ///
+    /// The counterpart to [`freeze()`](Self::freeze). The payload is never
+    /// cloned.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the container is now `Updatable` (thawing an
+    ///   `Updatable` or `Lazy` is a no-op)
+    /// - `Err(AnyCowReplaceError)` for `Borrowed` and `Owned` variants, which are left unchanged
+    pub fn thaw(&mut self) -> Result<(), AnyCowReplaceError> {
+        match self {
+            AnyCow::Shared(value) => {
+                *self = AnyCow::Updatable(ArcSwap::from(value.clone()));
+                Ok(())
+            }
+            AnyCow::Updatable(_) | AnyCow::Tracked(_) | AnyCow::Lazy { .. } => Ok(()),
+            AnyCow::Borrowed(_) | AnyCow::Owned(_) => Err(AnyCowReplaceError),
+        }
+    }
+
+    /// Moves the inner `Arc` out of this `AnyCow` without cloning.
+    ///
+    /// Unlike [`to_arc()`](Self::to_arc), which always produces an `Arc` and
+    /// clones the data when there is none, this fails instead. That makes it
+    /// a cheap way to find out whether a shared handle exists at all.
+    /// `Updatable` and initialized `Lazy` variants hand out their current
+    /// snapshot.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(arc)` for `Shared`, `Updatable` and initialized `Lazy` variants
+    /// - `Err(self)` for `Borrowed`, `Owned` and uninitialized `Lazy` variants
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use std::sync::Arc;
+    ///
+    /// let data = Arc::new(vec![1, 2, 3]);
+    /// let cow = AnyCow::shared(data.clone());
+    /// assert!(Arc::ptr_eq(&cow.into_inner_arc().unwrap(), &data));
+    ///
+    /// let cow = AnyCow::owned(vec![1, 2, 3]);
+    /// assert!(cow.into_inner_arc().unwrap_err().is_owned());
+    /// ```
+    pub fn into_inner_arc(self) -> Result<Arc<T>, Self> {
+        match self {
+            AnyCow::Shared(value) => Ok(value),
+            AnyCow::Updatable(value) => Ok(value.into_inner()),
+            AnyCow::Tracked(cell) if cell.is_initialized() => Ok(cell.into_inner()),
+            AnyCow::Lazy { data, init } => match data.into_inner() {
+                Some(value) => Ok(value.into_inner()),
+                None => Err(AnyCow::Lazy {
+                    data: OnceLock::new(),
+                    init,
+                }),
+            },
+            other => Err(other),
+        }
+    }
+
+    /// Consumes this `AnyCow` and leaks its inner `Arc` as a raw pointer.
+    ///
+    /// This mirrors `Arc::into_raw` and is meant for handing `Arc`-backed
+    /// data across an FFI boundary without cloning it. It succeeds for
+    /// `Shared`, `Updatable` and initialized `Lazy` variants (the latter two
+    /// leak their current snapshot). The pointer must eventually be passed
+    /// to [`from_raw_shared()`](Self::from_raw_shared) to avoid a leak.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(ptr)` if the container held an `Arc`
+    /// - `Err(self)` for `Borrowed`, `Owned` and uninitialized `Lazy` variants
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use std::sync::Arc;
+    ///
+    /// let cow = AnyCow::shared(Arc::new(String::from("ffi")));
+    /// let ptr = cow.into_raw_shared().unwrap();
+    ///
+    /// // SAFETY: `ptr` came from `into_raw_shared` and is reclaimed once
+    /// let cow = unsafe { AnyCow::from_raw_shared(ptr) };
+    /// assert_eq!(*cow.borrow(), "ffi");
+    /// ```
+    pub fn into_raw_shared(self) -> Result<*const T, Self> {
+        self.into_inner_arc().map(Arc::into_raw)
+    }
+
+    /// Reconstructs a `Shared` `AnyCow` from a pointer returned by
+    /// [`into_raw_shared()`](Self::into_raw_shared).
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been obtained from `into_raw_shared` (or
+    /// `Arc::into_raw`) for the same `T`, and each pointer may only be
+    /// reclaimed once. See `Arc::from_raw` for the full contract.
+    pub unsafe fn from_raw_shared(ptr: *const T) -> Self {
+        // SAFETY: upheld by the caller, see above.
+        AnyCow::Shared(unsafe { Arc::from_raw(ptr) })
+    }
+
+    /// Combines this value with `other` into a new `Owned` `AnyCow`.
+    ///
+    /// Both values are read (initializing `Lazy` variants) and passed to
+    /// `f`, whose result becomes the merged value. Useful for layering an
+    /// override configuration on top of a default one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let defaults = AnyCow::lazy(|| vec![("timeout", 30), ("retries", 3)]);
+    /// let overrides = AnyCow::owned(vec![("timeout", 5)]);
+    ///
+    /// let merged = overrides.merge(&defaults, |overrides, defaults| {
+    ///     defaults
+    ///         .iter()
+    ///         .map(|(key, value)| {
+    ///             let value = overrides.iter().find(|(k, _)| k == key).map_or(*value, |(_, v)| *v);
+    ///             (*key, value)
+    ///         })
+    ///         .collect()
+    /// });
+    /// assert!(merged.is_owned());
+    /// assert_eq!(*merged.borrow(), vec![("timeout", 5), ("retries", 3)]);
+    /// ```
+    pub fn merge<F>(self, other: &Self, f: F) -> AnyCow<'static, T>
+    where
+        T: 'static,
+        F: FnOnce(&T, &T) -> T,
+    {
+        AnyCow::Owned(Box::new(f(&self.borrow(), &other.borrow())))
+    }
+
+    /// Bundles this value and `other` into a new `Owned` pair.
+    ///
+    /// Both inputs are turned into owned values as with
+    /// [`into_owned()`](Self::into_owned), so `Owned` data and unshared
+    /// `Arc`s are moved rather than cloned. The inverse of
+    /// [`unzip()`](AnyCow::unzip).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use std::sync::Arc;
+    ///
+    /// let hosts = AnyCow::updatable(vec![String::from("a.example")]);
+    /// let port = AnyCow::lazy(|| 443u16);
+    ///
+    /// let snapshot = hosts.zip(port).to_arc();
+    /// assert_eq!(snapshot.1, 443);
+    /// assert_eq!(snapshot.0, vec![String::from("a.example")]);
+    /// ```
+    pub fn zip<U>(self, other: AnyCow<'_, U>) -> AnyCow<'static, (T, U)>
+    where
+        T: 'static + Clone,
+        U: 'static + Clone,
+    {
+        AnyCow::Owned(Box::new((self.into_owned(), other.into_owned())))
+    }
+
+    /// Turns a `Shared` variant whose `Arc` has no other strong or weak
+    /// reference into `Owned` by moving the value out, without cloning.
+    ///
+    /// Returns `false` and leaves the container unchanged otherwise.
+    fn unwrap_unique_shared(&mut self) -> bool {
+        let AnyCow::Shared(arc) = self else {
+            return false;
+        };
+        if Arc::get_mut(arc).is_none() {
+            return false;
+        }
+        // Nothing that can panic or read the container runs while the
+        // placeholder is installed: `try_unwrap` can't fail on an `Arc`
+        // that `&mut self` holds exclusively, and is never followed by a
+        // clone.
+        let placeholder = AnyCow::Lazy {
+            data: OnceLock::new(),
+            init: || unreachable!("the placeholder is replaced before it is read"),
+        };
+        let (next, moved) = match std::mem::replace(self, placeholder) {
+            AnyCow::Shared(arc) => match Arc::try_unwrap(arc) {
+                Ok(value) => (AnyCow::Owned(Box::new(value)), true),
+                Err(arc) => (AnyCow::Shared(arc), false),
+            },
+            other => (other, false),
+        };
+        *self = next;
+        moved
+    }
+}
+
+/// Replaces the value of every `Updatable` and `Lazy` container in `cows`.
+///
+/// `new` is wrapped in a single `Arc` that all targets share, so the value
+/// is never cloned no matter how many containers receive it. Other
+/// variants are skipped. Each container is replaced atomically, but not
+/// all of them at once: a reader looking at two containers may briefly see
+/// the new value in one and the old value in the other.
+///
+/// Returns the number of containers that were updated. See
+/// [`replace_all_arc()`] to broadcast an existing `Arc`.
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::AnyCow;
+/// use std::sync::Arc;
+///
+/// let subscribers = vec![
+///     AnyCow::updatable(String::from("v1")),
+///     AnyCow::owned(String::from("pinned")),
+///     AnyCow::updatable(String::from("v1")),
+/// ];
+///
+/// assert_eq!(anycow::replace_all(&subscribers, String::from("v2")), 2);
+/// assert_eq!(*subscribers[0].borrow(), "v2");
+/// assert_eq!(*subscribers[1].borrow(), "pinned");
+/// assert!(Arc::ptr_eq(&subscribers[0].to_arc(), &subscribers[2].to_arc()));
+/// ```
+pub fn replace_all<T>(cows: &[AnyCow<'_, T>], new: T) -> usize
+where
+    T: ToOwned<Owned = T>,
+{
+    replace_all_arc(cows, Arc::new(new))
+}
+
+/// Like [`replace_all()`], but stores clones of an existing `Arc`.
+pub fn replace_all_arc<T>(cows: &[AnyCow<'_, T>], new: Arc<T>) -> usize
+where
+    T: ToOwned<Owned = T>,
+{
+    cows.iter()
+        .filter(|cow| cow.try_replace_arc(new.clone()).is_ok())
+        .count()
+}
+
+/// Moves the value out of `shared`, cloning it only if `shared` isn't
+/// unique.
+pub(crate) fn unwrap_or_to_owned<T, P>(shared: P) -> T
+where
+    T: ToOwned<Owned = T>,
+    P: RefCount<T>,
+{
+    P::try_unwrap(shared).unwrap_or_else(|shared| T::to_owned(&shared))
+}
+
+/// Accessors for `String` payloads.
+impl<'a> AnyCow<'a, String> {
+    /// Returns a reference to the contained string as a `&str`.
+    ///
+    /// The returned [`MappedRef`] keeps the `Updatable` or `Lazy` guard
+    /// alive for as long as the string slice is in use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let cow = AnyCow::updatable(String::from("hello"));
+    /// assert_eq!(&*cow.as_str(), "hello");
+    /// ```
+    pub fn as_str(&self) -> MappedRef<'_, String, str> {
+        AnyCowRef::map(self.borrow(), String::as_str)
+    }
+
+    /// Copies the contained string into an `Arc<str>`.
+    ///
+    /// Unlike [`to_arc()`](Self::to_arc), which yields an `Arc<String>`
+    /// pointing at a second heap allocation, the string data lives directly
+    /// inside the `Arc`. This saves a pointer hop and interoperates with
+    /// APIs that expect `Arc<str>`, at the cost of copying the bytes once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use std::sync::Arc;
+    ///
+    /// let cow = AnyCow::updatable(String::from("key"));
+    /// let key: Arc<str> = cow.to_arc_str();
+    /// assert_eq!(&*key, "key");
+    /// ```
+    pub fn to_arc_str(&self) -> Arc<str> {
+        Arc::from(&*self.as_str())
+    }
+}
+
+/// Accessors for `Vec<T>` payloads.
+impl<'a, T> AnyCow<'a, Vec<T>>
+where
+    T: 'a + Clone,
+{
+    /// Returns a reference to the contained vector as a slice.
+    ///
+    /// The returned [`MappedRef`] keeps the `Updatable` or `Lazy` guard
+    /// alive for as long as the slice is in use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let cow = AnyCow::lazy(|| vec![1, 2, 3]);
+    /// assert_eq!(&*cow.as_slice(), &[1, 2, 3]);
+    /// ```
+    pub fn as_slice(&self) -> MappedRef<'_, Vec<T>, [T]> {
+        AnyCowRef::map(self.borrow(), Vec::as_slice)
+    }
+
+    /// Copies the contained elements into an `Arc<[T]>`.
+    ///
+    /// Unlike [`to_arc()`](Self::to_arc), which yields an `Arc<Vec<T>>`
+    /// pointing at a second heap allocation, the elements live directly
+    /// inside the `Arc`. Handy for shared network buffers (`Arc<[u8]>`)
+    /// and other APIs built around reference-counted slices.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use std::sync::Arc;
+    ///
+    /// let cow = AnyCow::owned(vec![1u8, 2, 3]);
+    /// let bytes: Arc<[u8]> = cow.to_arc_slice();
+    /// assert_eq!(&*bytes, &[1, 2, 3]);
+    /// ```
+    pub fn to_arc_slice(&self) -> Arc<[T]> {
+        Arc::from(&*self.as_slice())
+    }
+
+    /// Returns the element with the largest key, without cloning anything.
+    ///
+    /// The returned [`MappedRef`] points into the collection, keeping the
+    /// `Updatable` or `Lazy` guard alive while it is in use, so the
+    /// element stays valid even if the value is replaced meanwhile. If
+    /// several elements are equally maximum, the last one is returned,
+    /// like `Iterator::max_by_key`. Returns `None` if the vector is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let servers = AnyCow::updatable(vec![("eu", 40), ("us", 75), ("ap", 12)]);
+    /// let best = servers.max_by_key(|&(_, score)| score).unwrap();
+    /// assert_eq!(best.0, "us");
+    /// ```
+    pub fn max_by_key<K, F>(&self, mut f: F) -> Option<MappedRef<'_, Vec<T>, T>>
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        AnyCowRef::filter_map(self.borrow(), |items| {
+            items.iter().max_by_key(|item| f(item))
+        })
+        .ok()
+    }
+
+    /// Returns the element with the smallest key, without cloning anything.
+    ///
+    /// The counterpart of [`max_by_key()`](Self::max_by_key); if several
+    /// elements are equally minimum, the first one is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let servers = AnyCow::updatable(vec![("eu", 40), ("us", 75), ("ap", 12)]);
+    /// let idle = servers.min_by_key(|&(_, load)| load).unwrap();
+    /// assert_eq!(idle.0, "ap");
+    /// ```
+    pub fn min_by_key<K, F>(&self, mut f: F) -> Option<MappedRef<'_, Vec<T>, T>>
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        AnyCowRef::filter_map(self.borrow(), |items| {
+            items.iter().min_by_key(|item| f(item))
+        })
+        .ok()
+    }
+
+    /// Returns the vector itself for mutation, cloning it into an `Owned`
+    /// vector first if needed.
+    ///
+    /// The mutable counterpart of [`as_slice()`](Self::as_slice), with the
+    /// same materialization as [`to_mut()`](Self::to_mut). It returns the
+    /// concrete `Vec`, so `push`, `reserve` and the rest of its API are
+    /// available.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let defaults = vec![1, 2];
+    /// let mut cow = AnyCow::borrowed(&defaults);
+    /// let items = cow.to_mut_vec();
+    /// items.reserve(8);
+    /// items.push(3);
+    ///
+    /// assert!(cow.is_owned());
+    /// assert_eq!(*cow.borrow(), vec![1, 2, 3]);
+    /// ```
+    pub fn to_mut_vec(&mut self) -> &mut Vec<T> {
+        self.to_mut()
+    }
+
+    /// Returns the elements as a mutable slice, cloning the vector into an
+    /// `Owned` one first if needed, like [`to_mut_vec()`](Self::to_mut_vec).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let mut cow = AnyCow::updatable(vec![3, 1, 2]);
+    /// cow.as_mut_slice().sort();
+    /// assert_eq!(*cow.borrow(), vec![1, 2, 3]);
+    /// ```
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.to_mut()
+    }
+
+    /// Consumes the container and returns the vector.
+    ///
+    /// The spelling of [`into_owned()`](Self::into_owned) for vector
+    /// payloads: an `Owned` vector is moved out, and the vector of a
+    /// `Shared`, `Updatable` or `Lazy` is moved out of its `Arc` when
+    /// nothing else references it. Otherwise, and for `Borrowed` data, the
+    /// vector is cloned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    /// use std::sync::Arc;
+    ///
+    /// let cow = AnyCow::shared(Arc::new(vec![1, 2, 3]));
+    /// let mut items = cow.into_vec();
+    /// items.push(4);
+    /// assert_eq!(items, [1, 2, 3, 4]);
+    /// ```
+    pub fn into_vec(self) -> Vec<T> {
+        self.into_owned()
+    }
+
+    /// Consumes the container and returns the elements as a boxed slice.
+    ///
+    /// Obtains the vector like [`into_vec()`](Self::into_vec) and converts
+    /// it with `Vec::into_boxed_slice`, which keeps the allocation but
+    /// shrinks it to fit if the vector has spare capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let cow = AnyCow::updatable(vec![1u8, 2, 3]);
+    /// let bytes: Box<[u8]> = cow.into_boxed_slice();
+    /// assert_eq!(&*bytes, &[1, 2, 3]);
+    /// ```
+    pub fn into_boxed_slice(self) -> Box<[T]> {
+        self.into_vec().into_boxed_slice()
+    }
+}
+
+/// Atomic helpers for optional `Updatable` payloads.
+impl<'a, T> AnyCow<'a, Option<T>>
+where
+    T: 'a + Clone,
+{
+    /// Atomically stores `Some(value)` into an `Updatable` or `Lazy`.
     ///
     /// # Returns
     ///
-    /// - `Ok(())` if the replacement was successful
+    /// - `Ok(())` if the value was stored
     /// - `Err(AnyCowReplaceError)` if this container is not an `Updatable` or `Lazy` variant
     ///
     /// # Examples
@@ -470,99 +3349,117 @@ where
     /// ```rust
     /// use anycow::AnyCow;
     ///
-    /// let updatable = AnyCow::updatable(vec![1, 2, 3]);
-    /// assert_eq!(*updatable.borrow(), vec![1, 2, 3]);
+    /// let proxy = AnyCow::updatable(None);
+    /// proxy.set_some(String::from("socks5://localhost")).unwrap();
+    /// assert_eq!(proxy.borrow().as_deref(), Some("socks5://localhost"));
+    /// ```
+    pub fn set_some(&self, value: T) -> Result<(), AnyCowReplaceError> {
+        self.try_replace(Some(value))
+    }
+
+    /// Atomically stores `None` into an `Updatable` or `Lazy`.
     ///
-    /// // Atomic replacement
-    /// assert!(updatable.try_replace(vec![4, 5, 6]).is_ok());
-    /// assert_eq!(*updatable.borrow(), vec![4, 5, 6]);
+    /// # Returns
     ///
-    /// // Also works with lazy variants
-    /// let lazy = AnyCow::lazy(|| vec![7, 8, 9]);
-    /// assert!(lazy.try_replace(vec![10, 11, 12]).is_ok());
-    /// assert_eq!(*lazy.borrow(), vec![10, 11, 12]);
+    /// - `Ok(())` if the value was cleared
+    /// - `Err(AnyCowReplaceError)` if this container is not an `Updatable` or `Lazy` variant
+    pub fn set_none(&self) -> Result<(), AnyCowReplaceError> {
+        self.try_replace(None)
+    }
+
+    /// Atomically takes the value out of an `Updatable` or `Lazy`, leaving
+    /// `None` behind.
     ///
-    /// // This will fail for other variants
-    /// let owned = AnyCow::owned(vec![1, 2, 3]);
-    /// assert!(owned.try_replace(vec![4, 5, 6]).is_err());
+    /// The value is moved out of its snapshot when no reader still holds
+    /// that snapshot, and cloned otherwise. Returns `None` if the value
+    /// was already `None`, if this container is not an `Updatable` or
+    /// `Lazy` variant, or if its validator rejects `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let pending = AnyCow::updatable(Some(vec![1, 2, 3]));
+    /// assert_eq!(pending.take_option().as_deref(), Some(&vec![1, 2, 3]));
+    /// assert_eq!(*pending.borrow(), None);
+    /// assert!(pending.take_option().is_none());
     /// ```
-    pub fn try_replace(&self, new_val: T) -> Result<(), AnyCowReplaceError> {
-        match self {
-            AnyCow::Updatable(a) => {
-                a.store(Arc::new(new_val));
-                Ok(())
-            }
-            AnyCow::Lazy { data, init } => {
-                let arc_swap = data.get_or_init(|| ArcSwap::from(Arc::new(init())));
-                arc_swap.store(Arc::new(new_val));
-                Ok(())
-            }
-            _ => Err(AnyCowReplaceError),
-        }
+    pub fn take_option(&self) -> Option<Arc<T>> {
+        let previous = self.as_updatable()?.swap(None).ok()?;
+        Arc::try_unwrap(previous)
+            .unwrap_or_else(|arc| arc.as_ref().clone())
+            .map(Arc::new)
     }
+}
 
-    /// Converts this `AnyCow` to an `Arc<T>`.
+/// Downcasting for type-erased payloads.
+///
+/// `Box<dyn Any>` isn't `Clone`, so it can't be an `AnyCow` payload; store
+/// type-erased values as `Arc<dyn Any + Send + Sync>` instead, which also
+/// keeps cloning and replacing them cheap.
+impl<'a> AnyCow<'a, Arc<dyn Any + Send + Sync>> {
+    /// Returns a reference to the contained value if it is a `U`.
     ///
-    /// This method will clone the data if necessary to create an `Arc`.
-    /// If the container already holds an `Arc` (in the `Shared` or `Updatable`
-    /// variants), it may reuse the existing `Arc`.
+    /// The returned [`MappedRef`] keeps the `Updatable` or `Lazy` guard
+    /// alive, so the reference stays valid even if the value is replaced
+    /// meanwhile. Returns `None` if the value is of another type.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use anycow::AnyCow;
+    /// use std::any::Any;
     /// use std::sync::Arc;
     ///
-    /// let cow = AnyCow::owned(String::from("hello"));
-    /// let arc: Arc<String> = cow.to_arc();
-    /// assert_eq!(*arc, "hello");
+    /// let plugin: AnyCow<Arc<dyn Any + Send + Sync>> =
+    ///     AnyCow::updatable(Arc::new(String::from("v1")));
+    ///
+    /// assert_eq!(plugin.downcast_ref::<String>().as_deref().map(String::as_str), Some("v1"));
+    /// assert!(plugin.downcast_ref::<u32>().is_none());
+    ///
+    /// plugin.try_replace(Arc::new(7u32)).unwrap();
+    /// assert_eq!(plugin.downcast_ref::<u32>().as_deref(), Some(&7));
     /// ```
-    pub fn to_arc(&self) -> Arc<T> {
-        match self {
-            AnyCow::Borrowed(value) => Arc::new((*value).to_owned()),
-            AnyCow::Owned(value) => Arc::new((**value).to_owned()),
-            AnyCow::Shared(value) => value.clone(),
-            AnyCow::Updatable(value) => value.load().to_owned(),
-            AnyCow::Lazy { data, init } => {
-                let arc_swap = data.get_or_init(|| ArcSwap::from(Arc::new(init())));
-                arc_swap.load().to_owned()
-            }
+    pub fn downcast_ref<U: 'static>(&self) -> Option<MappedRef<'_, Arc<dyn Any + Send + Sync>, U>> {
+        let value = self.borrow();
+        if !(**value).is::<U>() {
+            return None;
         }
+        Some(AnyCowRef::map(value, |any| {
+            any.downcast_ref::<U>()
+                .expect("type was checked before mapping")
+        }))
     }
+}
 
-    /// Converts this `AnyCow` to a shared variant.
-    ///
-    /// Borrowed stays borrowed (no heap allocation).
-    /// All other variants allocate or clone into an Arc<T>.
-    /// Calling to_shared on a Lazy will force initialization.
+/// Combinators for pair payloads.
+impl<'a, A, B> AnyCow<'a, (A, B)>
+where
+    A: 'static + Clone,
+    B: 'static + Clone,
+{
+    /// Splits a pair into two independent `Owned` containers.
     ///
-    /// # Conversion behavior:
-    /// - `Borrowed` → stays `Borrowed` (zero-cost)
-    /// - `Owned` → converts to `Shared` with `Arc`
-    /// - `Shared` → returns a clone (cheap `Arc` clone)
-    /// - `Updatable` → converts to `Shared` with current value
-    /// - `Lazy` → initializes if needed and converts to `Shared`
+    /// An `Owned` pair, or a `Shared` one without other references, is
+    /// moved apart; otherwise each half is cloned out of the current
+    /// value, initializing a `Lazy` first.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use anycow::AnyCow;
-    /// use std::sync::Arc;
     ///
-    /// let data = "hello";
-    /// let borrowed = AnyCow::borrowed(&data);
-    /// let shared = borrowed.to_shared();
-    /// assert!(shared.is_borrowed()); // Still borrowed!
+    /// let stats = AnyCow::lazy(|| (String::from("p99"), 42u64));
+    /// let (name, value) = stats.unzip();
     ///
-    /// let owned = AnyCow::owned(String::from("world"));
-    /// let shared = owned.to_shared();
-    /// assert!(shared.is_shared()); // Now shared
+    /// assert!(name.is_owned() && value.is_owned());
+    /// assert_eq!(*name.borrow(), "p99");
+    /// assert_eq!(*value.borrow(), 42);
     /// ```
-    pub fn to_shared(&self) -> AnyCow<'a, T> {
-        match self {
-            AnyCow::Borrowed(value) => AnyCow::Borrowed(value),
-            _ => AnyCow::Shared(self.to_arc()),
-        }
+    pub fn unzip(self) -> (AnyCow<'static, A>, AnyCow<'static, B>) {
+        let (a, b) = self.into_owned();
+        (AnyCow::owned(a), AnyCow::owned(b))
     }
 }
 
@@ -588,49 +3485,325 @@ where
     }
 }
 
-/// Automatic conversion from borrowed references.
-///
-/// This implementation allows borrowed references to be automatically
-/// converted into an `AnyCow::Borrowed` variant.
+/// Automatic conversion from an already boxed value.
+///
+/// The existing `Box<T>` is reused as the `AnyCow::Owned` storage,
+/// so the value is neither moved out of its allocation nor re-boxed.
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::AnyCow;
+///
+/// let boxed = Box::new(String::from("hello"));
+/// let cow: AnyCow<String> = boxed.into();
+/// assert!(cow.is_owned());
+/// ```
+impl<T> From<Box<T>> for AnyCow<'_, T>
+where
+    T: ToOwned,
+{
+    fn from(value: Box<T>) -> Self {
+        AnyCow::Owned(value)
+    }
+}
+
+/// Automatic conversion from an array into an owned `Vec`.
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::AnyCow;
+///
+/// let cow: AnyCow<Vec<i32>> = [1, 2, 3].into();
+/// assert!(cow.is_owned());
+/// assert_eq!(*cow.borrow(), vec![1, 2, 3]);
+/// ```
+impl<T, const N: usize> From<[T; N]> for AnyCow<'_, Vec<T>>
+where
+    T: Clone,
+{
+    fn from(value: [T; N]) -> Self {
+        AnyCow::Owned(Box::new(Vec::from(value)))
+    }
+}
+
+/// Automatic conversion from a slice into an owned `Vec`.
+///
+/// `AnyCow` can't borrow a `[T]` as a `Vec<T>`, so the elements are
+/// cloned into a new `Vec`. Borrow an existing `Vec` instead to avoid the
+/// copy.
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::AnyCow;
+///
+/// let data = [1, 2, 3, 4];
+/// let cow: AnyCow<Vec<i32>> = data[1..3].into();
+/// assert!(cow.is_owned());
+/// assert_eq!(*cow.borrow(), vec![2, 3]);
+/// ```
+impl<T> From<&[T]> for AnyCow<'_, Vec<T>>
+where
+    T: Clone,
+{
+    fn from(value: &[T]) -> Self {
+        AnyCow::Owned(Box::new(value.to_vec()))
+    }
+}
+
+/// Automatic conversion from borrowed references.
+///
+/// This implementation allows borrowed references to be automatically
+/// converted into an `AnyCow::Borrowed` variant.
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::AnyCow;
+///
+/// let data = String::from("hello");
+/// let cow: AnyCow<String> = (&data).into();
+/// assert!(cow.is_borrowed());
+/// ```
+impl<'a, T> From<&'a T> for AnyCow<'a, T>
+where
+    T: 'a + ToOwned,
+{
+    fn from(value: &'a T) -> Self {
+        AnyCow::Borrowed(value)
+    }
+}
+
+/// Automatic conversion from `Arc<T>`.
+///
+/// This implementation allows `Arc<T>` values to be automatically
+/// converted into an `AnyCow::Shared` variant.
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::AnyCow;
+/// use std::sync::Arc;
+///
+/// let arc = Arc::new(String::from("hello"));
+/// let cow: AnyCow<String> = arc.into();
+/// ```
+impl<T> From<Arc<T>> for AnyCow<'_, T>
+where
+    T: ToOwned,
+{
+    fn from(value: Arc<T>) -> Self {
+        AnyCow::Shared(value)
+    }
+}
+
+/// Conversion into a standard `Cow`.
+///
+/// A `Borrowed` variant stays borrowed; every other variant becomes
+/// `Cow::Owned` through [`AnyCow::into_owned()`].
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::AnyCow;
+/// use std::borrow::Cow;
+///
+/// let name = String::from("config.toml");
+/// let cow: Cow<String> = AnyCow::borrowed(&name).into();
+/// assert!(matches!(cow, Cow::Borrowed(_)));
+/// ```
+impl<'a, T> From<AnyCow<'a, T>> for Cow<'a, T>
+where
+    T: 'a + ToOwned<Owned = T>,
+{
+    fn from(cow: AnyCow<'a, T>) -> Self {
+        match cow {
+            AnyCow::Borrowed(value) => Cow::Borrowed(value),
+            other => Cow::Owned(other.into_owned()),
+        }
+    }
+}
+
+/// Conversion from an [`UpdatableCell`] into a `Tracked` variant.
+///
+/// Together with [`UpdatableCell::builder()`] this creates containers that
+/// combine several policies, such as a history and a validator.
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::{AnyCow, UpdatableCell};
+///
+/// let cow: AnyCow<u32> = UpdatableCell::builder(1).history(2).build().into();
+/// cow.try_replace(2).unwrap();
+/// assert_eq!(*cow.history()[0], 1);
+/// ```
+impl<T> From<UpdatableCell<T>> for AnyCow<'_, T>
+where
+    T: ToOwned,
+{
+    fn from(cell: UpdatableCell<T>) -> Self {
+        AnyCow::Tracked(Box::new(cell))
+    }
+}
+
+/// Extending the contained collection.
+///
+/// - `Owned`: Extends the data in place, without cloning
+/// - `Borrowed` / `Shared`: Clones the data into `Owned` first, like [`AnyCow::to_mut()`]
+/// - `Updatable` / `Lazy`: Clones the current value, extends the copy and
+///   atomically stores it back, keeping the variant
+///
+/// The `Updatable` path is copy-on-write: every call clones the whole
+/// collection once, so prefer batching items into a single `extend`.
+/// `Extend` can't report errors, so if the validator of an
+/// [`AnyCow::updatable_validated()`] container rejects the extended
+/// collection, the current value stays in place.
+/// [`UpdatableCollectionExt::insert_many()`] reports that the update
+/// failed, and [`AnyCow::try_replace_validated()`] with the extended
+/// collection also hands back the validator's reason.
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::AnyCow;
+///
+/// let mut buffer = AnyCow::updatable(vec![1, 2]);
+/// buffer.extend([3, 4]);
+/// assert!(buffer.is_updatable());
+/// assert_eq!(*buffer.borrow(), vec![1, 2, 3, 4]);
+/// ```
+impl<'a, T, A> Extend<A> for AnyCow<'a, T>
+where
+    T: 'a + ToOwned<Owned = T> + Extend<A>,
+{
+    fn extend<I: IntoIterator<Item = A>>(&mut self, iter: I) {
+        match self.as_updatable() {
+            Some(handle) => {
+                let mut value = T::to_owned(&handle.load());
+                value.extend(iter);
+                // Rejected values are dropped, see above.
+                let _ = handle.store(value);
+            }
+            None => self.to_mut().extend(iter),
+        }
+    }
+}
+
+/// String concatenation, materializing the result as `Owned`.
+///
+/// Like `Cow<str> + &str`, the left-hand side is converted into an owned
+/// `String` (reusing the allocation of an `Owned` or uniquely held `Shared`
+/// value) and the result is `Owned`, whatever the original variant was.
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::AnyCow;
+///
+/// let greeting = String::from("hello");
+/// let cow = AnyCow::borrowed(&greeting) + ", world";
+/// assert!(cow.is_owned());
+/// assert_eq!(*cow.borrow(), "hello, world");
+/// ```
+impl<'a> Add<&str> for AnyCow<'a, String> {
+    type Output = AnyCow<'a, String>;
+
+    fn add(self, rhs: &str) -> Self::Output {
+        let mut value = self.into_owned();
+        value.push_str(rhs);
+        AnyCow::Owned(Box::new(value))
+    }
+}
+
+/// In-place string concatenation.
+///
+/// `Updatable` and `Lazy` variants atomically store the concatenated
+/// string and keep their variant, retrying if another writer races with
+/// the update. Every other variant is converted to `Owned` first, like
+/// [`AnyCow::to_mut()`].
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::AnyCow;
+///
+/// let mut log = AnyCow::updatable(String::from("a"));
+/// log += "b";
+/// assert!(log.is_updatable());
+/// assert_eq!(*log.borrow(), "ab");
+/// ```
+impl AddAssign<&str> for AnyCow<'_, String> {
+    fn add_assign(&mut self, rhs: &str) {
+        match self.as_updatable() {
+            Some(handle) => {
+                // Like `Extend`, a rejected value leaves the current one in place.
+                let _ = handle.rcu(|current| {
+                    let mut value = String::with_capacity(current.len() + rhs.len());
+                    value.push_str(current);
+                    value.push_str(rhs);
+                    value
+                });
+            }
+            None => self.to_mut().push_str(rhs),
+        }
+    }
+}
+
+/// `Vec` concatenation, materializing the result as `Owned`.
 ///
 /// # Examples
 ///
 /// ```rust
 /// use anycow::AnyCow;
 ///
-/// let data = String::from("hello");
-/// let cow: AnyCow<String> = (&data).into();
-/// assert!(cow.is_borrowed());
+/// let cow = AnyCow::owned(vec![1, 2]) + &[3, 4][..];
+/// assert_eq!(*cow.borrow(), vec![1, 2, 3, 4]);
 /// ```
-impl<'a, T> From<&'a T> for AnyCow<'a, T>
+impl<'a, T> Add<&[T]> for AnyCow<'a, Vec<T>>
 where
-    T: 'a + ToOwned,
+    T: 'a + Clone,
 {
-    fn from(value: &'a T) -> Self {
-        AnyCow::Borrowed(value)
+    type Output = AnyCow<'a, Vec<T>>;
+
+    fn add(self, rhs: &[T]) -> Self::Output {
+        let mut value = self.into_owned();
+        value.extend_from_slice(rhs);
+        AnyCow::Owned(Box::new(value))
     }
 }
 
-/// Automatic conversion from `Arc<T>`.
-///
-/// This implementation allows `Arc<T>` values to be automatically
-/// converted into an `AnyCow::Shared` variant.
+/// In-place `Vec` concatenation, atomic for `Updatable` and `Lazy`
+/// variants in the same way as the `String` impl.
 ///
 /// # Examples
 ///
 /// ```rust
 /// use anycow::AnyCow;
-/// use std::sync::Arc;
 ///
-/// let arc = Arc::new(String::from("hello"));
-/// let cow: AnyCow<String> = arc.into();
+/// let mut items = AnyCow::updatable(vec![1]);
+/// items += &[2, 3][..];
+/// assert_eq!(*items.borrow(), vec![1, 2, 3]);
 /// ```
-impl<T> From<Arc<T>> for AnyCow<'_, T>
+impl<'a, T> AddAssign<&[T]> for AnyCow<'a, Vec<T>>
 where
-    T: ToOwned,
+    T: 'a + Clone,
 {
-    fn from(value: Arc<T>) -> Self {
-        AnyCow::Shared(value)
+    fn add_assign(&mut self, rhs: &[T]) {
+        match self.as_updatable() {
+            Some(handle) => {
+                // Like `Extend`, a rejected value leaves the current one in place.
+                let _ = handle.rcu(|current| {
+                    let mut value = Vec::with_capacity(current.len() + rhs.len());
+                    value.extend_from_slice(current);
+                    value.extend_from_slice(rhs);
+                    value
+                });
+            }
+            None => self.to_mut().extend_from_slice(rhs),
+        }
     }
 }
 
@@ -684,6 +3857,150 @@ where
     }
 }
 
+impl<'a, T> AnyCowRef<'a, T>
+where
+    T: 'a + ToOwned,
+{
+    /// Projects this reference to a part of the contained data.
+    ///
+    /// The returned [`MappedRef`] keeps the original reference (and its
+    /// guard, if any) alive, so the projection stays valid for as long as
+    /// the `MappedRef` itself.
+    ///
+    /// This is an associated function rather than a method so it doesn't
+    /// shadow methods of `T` reachable through `Deref`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::{AnyCow, AnyCowRef};
+    ///
+    /// let cow = AnyCow::updatable((1, String::from("second")));
+    /// let second = AnyCowRef::map(cow.borrow(), |pair| &pair.1);
+    /// assert_eq!(*second, "second");
+    /// ```
+    pub fn map<U, F>(this: Self, f: F) -> MappedRef<'a, T, U>
+    where
+        U: ?Sized,
+        F: FnOnce(&T) -> &U,
+    {
+        let value = NonNull::from(f(&*this));
+        MappedRef {
+            _owner: this,
+            value,
+        }
+    }
+
+    /// Projects this reference to a part of the contained data that may
+    /// not exist.
+    ///
+    /// Like [`map()`](Self::map), but `f` returns an `Option`; if it
+    /// returns `None`, the original reference is handed back. This mirrors
+    /// `std::cell::Ref::filter_map`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::{AnyCow, AnyCowRef};
+    ///
+    /// let cow = AnyCow::updatable(vec![1, 2, 3]);
+    /// let first_even = AnyCowRef::filter_map(cow.borrow(), |v| v.iter().find(|&&n| n % 2 == 0));
+    /// assert_eq!(*first_even.ok().unwrap(), 2);
+    /// ```
+    pub fn filter_map<U, F>(this: Self, f: F) -> Result<MappedRef<'a, T, U>, Self>
+    where
+        U: ?Sized,
+        F: FnOnce(&T) -> Option<&U>,
+    {
+        match f(&*this).map(NonNull::from) {
+            Some(value) => Ok(MappedRef {
+                _owner: this,
+                value,
+            }),
+            None => Err(this),
+        }
+    }
+}
+
+impl<'a, T> AnyCowRef<'a, T>
+where
+    T: 'a + ToOwned<Owned = T>,
+{
+    /// Returns an owned copy of the referenced data.
+    ///
+    /// Shorthand for `(*this).to_owned()`. Like [`map()`](Self::map), this
+    /// is an associated function so it doesn't shadow methods of `T` such
+    /// as `Option::cloned`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::{AnyCow, AnyCowRef};
+    ///
+    /// let cow = AnyCow::updatable(vec![1, 2, 3]);
+    /// let copy: Vec<i32> = AnyCowRef::cloned(&cow.borrow());
+    /// assert_eq!(copy, vec![1, 2, 3]);
+    /// ```
+    pub fn cloned(this: &Self) -> T {
+        (**this).to_owned()
+    }
+
+    /// Converts the reference into an `Arc`.
+    ///
+    /// A `Guarded` reference already points into an `Arc`, which is reused
+    /// without cloning the value, so the result is the same snapshot the
+    /// guard was protecting. A `Direct` reference is cloned into a new
+    /// `Arc`, including one borrowed from a `Shared` variant; use
+    /// [`AnyCow::to_arc()`] to reuse the `Arc` of a `Shared` variant.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::{AnyCow, AnyCowRef};
+    /// use std::sync::Arc;
+    ///
+    /// let cow = AnyCow::updatable(String::from("config"));
+    /// let snapshot = AnyCowRef::into_arc(cow.borrow());
+    /// assert!(Arc::ptr_eq(&snapshot, &cow.to_arc()));
+    /// ```
+    pub fn into_arc(this: Self) -> Arc<T> {
+        match this {
+            AnyCowRef::Direct(value) => Arc::new(value.to_owned()),
+            AnyCowRef::Guarded(guard) => Guard::into_inner(guard),
+        }
+    }
+}
+
+/// A reference to a part of the data contained in an `AnyCow`.
+///
+/// Created by [`AnyCowRef::map()`] and the payload-specific accessors
+/// such as [`AnyCow::as_str()`]. It owns the underlying [`AnyCowRef`],
+/// so guarded data stays alive while the projection is in use.
+pub struct MappedRef<'a, T, U>
+where
+    T: 'a + ToOwned,
+    U: ?Sized,
+{
+    _owner: AnyCowRef<'a, T>,
+    value: NonNull<U>,
+}
+
+impl<'a, T, U> Deref for MappedRef<'a, T, U>
+where
+    T: 'a + ToOwned,
+    U: ?Sized,
+{
+    type Target = U;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: `value` was derived from a reference to the data behind
+        // `_owner`. That data is either borrowed for `'a` or lives in an
+        // `Arc` kept alive by the guard in `_owner`, so it doesn't move or
+        // get dropped while `self` exists.
+        unsafe { self.value.as_ref() }
+    }
+}
+
 /// Cloning support for `AnyCow`.
 ///
 /// Cloning behavior varies by variant:
@@ -696,6 +4013,23 @@ where
 /// Note: Cloning a `Lazy` variant will trigger initialization if it hasn't
 /// happened yet, and the resulting clone will be an `Updatable` variant.
 /// This ensures that cloned data is immediately ready for use.
+///
+/// `clone_from` reuses the destination's allocation when both sides are
+/// `Owned`: the `Box` is kept and the value is updated with `T::clone_from`,
+/// so e.g. a `Vec` or `String` keeps its capacity.
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::AnyCow;
+///
+/// let source = AnyCow::owned(vec![1, 2, 3]);
+/// let mut target = AnyCow::owned(Vec::with_capacity(64));
+///
+/// target.clone_from(&source);
+/// assert_eq!(*target.borrow(), vec![1, 2, 3]);
+/// assert_eq!(target.borrow().capacity(), 64);
+/// ```
 impl<'a, T> Clone for AnyCow<'a, T>
 where
     T: 'a + ToOwned<Owned = T> + Clone,
@@ -708,18 +4042,57 @@ where
             AnyCow::Updatable(value) => {
                 // Create a new Updatable with a snapshot of the current data
                 // This maintains updatable semantics for the clone
-                AnyCow::Updatable(ArcSwap::from(value.load().clone()))
+                AnyCow::Updatable(ArcSwap::new(value.load_full()))
+            }
+            AnyCow::Tracked(cell) => {
+                // The clone starts counting afresh and doesn't inherit the
+                // history, validator or other policies
+                AnyCow::Tracked(Box::new(UpdatableCell::from_arc(cell.load_full())))
             }
             AnyCow::Lazy { data, init } => {
                 // Always initialize the lazy data when cloning to ensure the clone
                 // has access to the actual data. This changes the clone from Lazy
                 // to Updatable, which is intentional - once we've decided to clone
                 // the data, we want it to be readily available.
-                let arc_swap = data.get_or_init(|| ArcSwap::from(Arc::new(init())));
-                AnyCow::Updatable(ArcSwap::from(arc_swap.load().clone()))
+                let value = data.get_or_init(|| ArcSwap::from_pointee(init()));
+                AnyCow::Updatable(ArcSwap::new(value.load_full()))
             }
         }
     }
+
+    fn clone_from(&mut self, source: &Self) {
+        match (self, source) {
+            (AnyCow::Owned(target), AnyCow::Owned(source)) => (**target).clone_from(source),
+            (target, source) => *target = source.clone(),
+        }
+    }
+}
+
+/// Path access for `PathBuf` payloads, so a borrowed `AnyCow` drops
+/// straight into `std::fs` calls.
+///
+/// This is implemented on [`AnyCowRef`] rather than `AnyCow` itself so it
+/// works for every variant, including `Updatable` and `Lazy` ones.
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::AnyCow;
+///
+/// let dir = AnyCow::updatable(std::env::temp_dir());
+/// assert!(std::fs::metadata(dir.borrow()).unwrap().is_dir());
+/// ```
+impl AsRef<Path> for AnyCowRef<'_, PathBuf> {
+    fn as_ref(&self) -> &Path {
+        self
+    }
+}
+
+/// `OsStr` access for `OsString` payloads.
+impl AsRef<OsStr> for AnyCowRef<'_, OsString> {
+    fn as_ref(&self) -> &OsStr {
+        self
+    }
 }
 
 /// Debug formatting for `AnyCow`.
@@ -735,9 +4108,13 @@ where
             AnyCow::Owned(value) => f.debug_tuple("Owned").field(&**value).finish(),
             AnyCow::Shared(value) => f.debug_tuple("Shared").field(value).finish(),
             AnyCow::Updatable(value) => f.debug_tuple("Updatable").field(&*value.load()).finish(),
+            AnyCow::Tracked(cell) if !cell.is_initialized() => {
+                f.debug_tuple("Tracked").field(&"<uninitialized>").finish()
+            }
+            AnyCow::Tracked(cell) => f.debug_tuple("Tracked").field(&*cell.load()).finish(),
             AnyCow::Lazy { data, .. } => {
-                if let Some(arc_swap) = data.get() {
-                    f.debug_tuple("Lazy").field(&*arc_swap.load()).finish()
+                if let Some(value) = data.get() {
+                    f.debug_tuple("Lazy").field(&*value.load()).finish()
                 } else {
                     f.debug_tuple("Lazy").field(&"<uninitialized>").finish()
                 }
@@ -746,15 +4123,79 @@ where
     }
 }
 
+impl<'a, T> AnyCow<'a, T>
+where
+    T: 'a + ToOwned<Owned = T> + std::fmt::Debug,
+{
+    /// Returns a value that debug-formats only the contained data.
+    ///
+    /// The regular `Debug` impl wraps the data in its variant name, which is
+    /// noise when logging values whose storage strategy doesn't matter.
+    /// Formatter flags such as `{:#?}` are forwarded to the inner value.
+    /// Like `Debug`, this doesn't initialize a `Lazy`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let cow = AnyCow::updatable(vec![1, 2]);
+    /// assert_eq!(format!("{:?}", cow), "Updatable([1, 2])");
+    /// assert_eq!(format!("{:?}", cow.debug_value()), "[1, 2]");
+    /// ```
+    pub fn debug_value(&self) -> impl std::fmt::Debug + '_ {
+        DebugValue(self)
+    }
+}
+
+/// Formats the data of an `AnyCow` without its variant name.
+struct DebugValue<'b, 'a, T>(&'b AnyCow<'a, T>)
+where
+    T: 'a + ToOwned<Owned = T>;
+
+impl<'b, 'a, T> std::fmt::Debug for DebugValue<'b, 'a, T>
+where
+    T: 'a + ToOwned<Owned = T> + std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            AnyCow::Lazy { data, .. } => match data.get() {
+                Some(value) => T::fmt(&value.load(), f),
+                None => f.write_str("<uninitialized>"),
+            },
+            AnyCow::Tracked(cell) if !cell.is_initialized() => f.write_str("<uninitialized>"),
+            cow => T::fmt(&cow.borrow(), f),
+        }
+    }
+}
+
 /// Equality comparison for `AnyCow`.
 ///
 /// Compares the contained data regardless of storage variant.
 /// Two `AnyCow` instances are equal if their contained data is equal.
-impl<'a, T> PartialEq for AnyCow<'a, T>
+///
+/// Like `Vec<T>: PartialEq<Vec<U>>`, the payload types may differ as long
+/// as they are comparable, so an `AnyCow<String>` can be compared with an
+/// `AnyCow<&str>`.
+///
+/// Initializes both sides if they are `Lazy`; see
+/// [Lazy initialization side effects](AnyCow#lazy-initialization-side-effects).
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::AnyCow;
+///
+/// let owned = AnyCow::owned(String::from("hello"));
+/// let literal = AnyCow::owned("hello");
+/// assert!(owned == literal);
+/// ```
+impl<'a, 'b, T, U> PartialEq<AnyCow<'b, U>> for AnyCow<'a, T>
 where
-    T: 'a + ToOwned<Owned = T> + PartialEq,
+    T: 'a + ToOwned<Owned = T> + PartialEq<U>,
+    U: 'b + ToOwned<Owned = U>,
 {
-    fn eq(&self, other: &Self) -> bool {
+    fn eq(&self, other: &AnyCow<'b, U>) -> bool {
         self.borrow().deref() == other.borrow().deref()
     }
 }
@@ -764,7 +4205,10 @@ impl<'a, T> Eq for AnyCow<'a, T> where T: 'a + ToOwned<Owned = T> + Eq {}
 
 /// Hash implementation for `AnyCow`.
 ///
-/// Hashes the contained data regardless of storage variant.
+/// Hashes the contained data regardless of storage variant, which
+/// initializes a `Lazy` variant. The value of an `Updatable` or `Lazy` is
+/// loaded once per call, so a replacement racing with `hash` never mixes
+/// parts of the old and the new value; see [`AnyCow::stable_hash()`].
 impl<'a, T> std::hash::Hash for AnyCow<'a, T>
 where
     T: 'a + ToOwned<Owned = T> + std::hash::Hash,
@@ -776,7 +4220,8 @@ where
 
 /// Partial ordering for `AnyCow`.
 ///
-/// Compares the contained data regardless of storage variant.
+/// Compares the contained data regardless of storage variant. Like `==`,
+/// this initializes `Lazy` operands.
 impl<'a, T> PartialOrd for AnyCow<'a, T>
 where
     T: 'a + ToOwned<Owned = T> + PartialOrd,
@@ -789,6 +4234,7 @@ where
 /// Total ordering for `AnyCow`.
 ///
 /// Orders based on the contained data regardless of storage variant.
+/// Like `==`, this initializes `Lazy` operands.
 impl<'a, T> Ord for AnyCow<'a, T>
 where
     T: 'a + ToOwned<Owned = T> + Ord,
@@ -810,5 +4256,247 @@ where
     }
 }
 
+/// An owned, immutable snapshot of an `AnyCow`'s value.
+///
+/// Created by [`AnyCow::snapshot()`]. Unlike [`AnyCowRef`], whose borrow
+/// depends on the variant it came from, a `Snapshot` is always backed by
+/// an `Arc<T>` and borrows nothing from the container, so it can be stored,
+/// cloned cheaply and sent to other threads (when `T: Send + Sync`).
+pub struct Snapshot<T>(Arc<T>);
+
+impl<T> Snapshot<T> {
+    /// Returns the backing `Arc`.
+    pub fn into_arc(self) -> Arc<T> {
+        self.0
+    }
+}
+
+impl<T> Deref for Snapshot<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> Clone for Snapshot<T> {
+    fn clone(&self) -> Self {
+        Snapshot(self.0.clone())
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Snapshot<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Snapshot").field(&*self.0).finish()
+    }
+}
+
+/// Identifies a snapshot of an `Updatable` or `Lazy` value.
+///
+/// Created by [`AnyCow::snapshot_token()`] and consumed by
+/// [`AnyCow::replace_if()`]. The token holds on to the snapshot it was
+/// taken from, which keeps comparisons free of ABA problems.
+pub struct Token<T>(Arc<T>);
+
+impl<T> Token<T> {
+    /// Returns the value that was current when the token was taken.
+    pub fn value(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> Clone for Token<T> {
+    fn clone(&self) -> Self {
+        Token(self.0.clone())
+    }
+}
+
+/// A pending edit of an `Updatable` or `Lazy` value.
+///
+/// Created by [`AnyCow::edit()`]. Dereferences to a private copy of the value
+/// taken when the edit started; dropping the editor without committing
+/// discards every change.
+pub struct Editor<'e, T> {
+    handle: UpdatableHandle<'e, T>,
+    base: Arc<T>,
+    value: T,
+}
+
+impl<T> Editor<'_, T>
+where
+    T: ToOwned<Owned = T>,
+{
+    /// Publishes the edited value in a single atomic store.
+    ///
+    /// The store only happens if the value hasn't been replaced since the
+    /// edit started, so concurrent writes are never silently overwritten.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the edited value was stored
+    /// - `Err(value)` with the edited value if another write happened in
+    ///   between, or if the validator rejected it
+    pub fn commit(self) -> Result<(), T> {
+        let Editor {
+            handle,
+            base,
+            value,
+        } = self;
+        handle
+            .compare_and_swap(&base, value)
+            .map(drop)
+            .map_err(|error| unwrap_or_to_owned(error.into_value()))
+    }
+
+    /// Returns the value the edit started from.
+    pub fn original(&self) -> &T {
+        &self.base
+    }
+}
+
+impl<T> Deref for Editor<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for Editor<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+/// Restores the value replaced by [`AnyCow::scoped_replace()`] on drop.
+///
+/// The restore is a single atomic store of the previous value; it happens
+/// regardless of any writes made since the guard was created, so cleanup
+/// is guaranteed.
+#[must_use = "the previous value is restored as soon as the guard is dropped"]
+pub struct ScopeGuard<'g, T> {
+    handle: UpdatableHandle<'g, T>,
+    previous: Option<Arc<T>>,
+}
+
+impl<T> ScopeGuard<'_, T> {
+    /// Keeps the temporary value instead of restoring the previous one.
+    pub fn commit(mut self) {
+        self.previous = None;
+    }
+
+    /// Returns the value that will be restored.
+    pub fn previous(&self) -> &T {
+        self.previous
+            .as_ref()
+            .expect("previous value is only taken by commit or drop")
+    }
+}
+
+impl<T> Drop for ScopeGuard<'_, T> {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            // The previous value was in place before, so only a validator
+            // that changed its mind can refuse it; keep the temporary one then.
+            let _ = self.handle.store_arc(previous);
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub struct AnyCowReplaceError;
+
+/// The error of [`AnyCow::owned_mut()`]: the data isn't `Owned`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct NotOwned;
+
+/// What [`AnyCow::to_mut_tracked()`] turned into the `Owned` value.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum MaterializedFrom {
+    /// The data was already `Owned`; nothing happened.
+    AlreadyOwned,
+
+    /// `Borrowed` data was cloned.
+    ClonedFromBorrowed,
+
+    /// The value of a `Shared` variant was moved out of its `Arc`, which
+    /// nothing else referenced, without cloning.
+    MovedFromShared,
+
+    /// The value of a `Shared` variant was cloned, since other references
+    /// to its `Arc` exist.
+    ClonedFromShared,
+
+    /// The current value of an `Updatable` variant was cloned.
+    ClonedFromUpdatable,
+
+    /// The current value of a `Lazy` variant was cloned, after running its
+    /// initializer if needed.
+    ClonedFromLazy,
+}
+
+impl MaterializedFrom {
+    /// Returns `true` if the value was cloned.
+    pub fn cloned(self) -> bool {
+        !matches!(
+            self,
+            MaterializedFrom::AlreadyOwned | MaterializedFrom::MovedFromShared
+        )
+    }
+}
+
+/// The error of [`AnyCow::try_replace_computed()`].
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ReplaceError<E> {
+    /// The container isn't an `Updatable` or `Lazy` variant, or its
+    /// validator rejected the computed value.
+    NotReplaceable,
+
+    /// Computing the new value failed.
+    Compute(E),
+}
+
+/// Whether [`AnyCow::replace_status()`] replaced an initialized value.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum WasInitialized {
+    /// An existing value, possibly already seen by readers, was replaced.
+    Yes,
+
+    /// The container was an uninitialized `Lazy`; the new value is its
+    /// first.
+    No,
+}
+
+impl From<WasInitialized> for bool {
+    fn from(status: WasInitialized) -> bool {
+        status == WasInitialized::Yes
+    }
+}
+
+/// A value refused by [`AnyCow::try_replace_validated()`].
+pub struct Rejected<T> {
+    value: T,
+    reason: Option<Box<dyn std::error::Error + Send + Sync>>,
+}
+
+impl<T> Rejected<T> {
+    /// Returns the validator's error, or `None` if the container wasn't an
+    /// `Updatable` or `Lazy` variant.
+    pub fn reason(&self) -> Option<&(dyn std::error::Error + Send + Sync + 'static)> {
+        self.reason.as_deref()
+    }
+
+    /// Returns the rejected value.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Rejected<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Rejected")
+            .field("value", &self.value)
+            .field("reason", &self.reason)
+            .finish()
+    }
+}
@@ -0,0 +1,289 @@
+//! Single-threaded sibling of [`AnyCow`](crate::AnyCow) backed by `Rc`.
+
+use std::ops::Deref;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// A reference-counted pointer that [`LocalAnyCow`] can share data through.
+///
+/// Implemented for `Rc<T>` and `Arc<T>`, so the same code serves both the
+/// single-threaded default and a `Send` container that only needs the
+/// `Borrowed`, `Owned` and `Shared` variants.
+pub trait RefCount<T>: Deref<Target = T> + Clone {
+    /// Wraps `value` in a new pointer.
+    fn new(value: T) -> Self;
+
+    /// Returns the value if `this` is its only strong reference, or `this`
+    /// unchanged otherwise.
+    fn try_unwrap(this: Self) -> Result<T, Self>;
+}
+
+impl<T> RefCount<T> for Rc<T> {
+    fn new(value: T) -> Self {
+        Rc::new(value)
+    }
+
+    fn try_unwrap(this: Self) -> Result<T, Self> {
+        Rc::try_unwrap(this)
+    }
+}
+
+impl<T> RefCount<T> for Arc<T> {
+    fn new(value: T) -> Self {
+        Arc::new(value)
+    }
+
+    fn try_unwrap(this: Self) -> Result<T, Self> {
+        Arc::try_unwrap(this)
+    }
+}
+
+/// A single-threaded counterpart to [`AnyCow`](crate::AnyCow) that shares
+/// data through `Rc<T>` instead of `Arc<T>`.
+///
+/// Applications that never share values across threads (GUIs, single-threaded
+/// caches, interpreters) pay for atomic reference counting on every clone of
+/// an `Arc`. `LocalAnyCow` keeps the Cow ergonomics without that cost. There
+/// is no `Updatable` or `Lazy` variant: both rely on `arc-swap`, which only
+/// works with `Arc`. A `LocalAnyCow` holding an `Rc` is neither `Send` nor
+/// `Sync`.
+///
+/// Because every variant is a direct reference, [`borrow()`](Self::borrow)
+/// returns a plain `&T`.
+///
+/// The pointer type `P` defaults to `Rc<T>`; any [`RefCount`] works, so
+/// `LocalAnyCow<'_, T, Arc<T>>` is a `Send` container with the same API.
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::LocalAnyCow;
+/// use std::rc::Rc;
+///
+/// let template = Rc::new(String::from("hello"));
+/// let a = LocalAnyCow::shared(template.clone());
+/// let mut b = a.clone();
+///
+/// b.to_mut().push_str(", world");
+/// assert_eq!(a.borrow(), "hello");
+/// assert_eq!(b.borrow(), "hello, world");
+/// assert_eq!(Rc::strong_count(&template), 2);
+/// ```
+pub enum LocalAnyCow<'a, T, P = Rc<T>>
+where
+    T: 'a + ToOwned,
+{
+    /// A borrowed reference to the data with zero allocation cost.
+    Borrowed(&'a T),
+
+    /// Heap-allocated owned data stored in a `Box<T>`.
+    Owned(Box<T>),
+
+    /// Reference-counted shared data, via `Rc<T>` by default.
+    Shared(P),
+}
+
+impl<'a, T> LocalAnyCow<'a, T>
+where
+    T: 'a + ToOwned<Owned = T>,
+{
+    /// Creates a new `LocalAnyCow` from a borrowed reference.
+    pub const fn borrowed(value: &'a T) -> Self {
+        LocalAnyCow::Borrowed(value)
+    }
+
+    /// Creates a new `LocalAnyCow` from an owned value.
+    pub fn owned(value: T) -> Self {
+        LocalAnyCow::Owned(Box::new(value))
+    }
+
+    /// Returns an `Rc<T>` to the data, cloning it into a new `Rc` unless it
+    /// is already shared.
+    pub fn to_rc(&self) -> Rc<T> {
+        self.to_shared()
+    }
+}
+
+impl<'a, T, P> LocalAnyCow<'a, T, P>
+where
+    T: 'a + ToOwned<Owned = T>,
+    P: RefCount<T>,
+{
+    /// Creates a new `LocalAnyCow` from a reference-counted pointer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::LocalAnyCow;
+    /// use std::rc::Rc;
+    ///
+    /// let cow = LocalAnyCow::shared(Rc::new(42));
+    /// assert!(cow.is_shared());
+    /// assert_eq!(*cow, 42);
+    /// ```
+    #[doc(alias = "shared_local")]
+    pub const fn shared(value: P) -> Self {
+        LocalAnyCow::Shared(value)
+    }
+
+    /// Returns `true` if the data is borrowed.
+    pub const fn is_borrowed(&self) -> bool {
+        matches!(self, LocalAnyCow::Borrowed(_))
+    }
+
+    /// Returns `true` if the data is owned.
+    pub const fn is_owned(&self) -> bool {
+        matches!(self, LocalAnyCow::Owned(_))
+    }
+
+    /// Returns `true` if the data is shared through a reference count.
+    pub const fn is_shared(&self) -> bool {
+        matches!(self, LocalAnyCow::Shared(_))
+    }
+
+    /// Returns a reference to the contained data.
+    ///
+    /// Named after [`AnyCow::borrow()`](crate::AnyCow::borrow) so code can
+    /// move between the two types unchanged.
+    #[allow(clippy::should_implement_trait)]
+    pub fn borrow(&self) -> &T {
+        match self {
+            LocalAnyCow::Borrowed(value) => value,
+            LocalAnyCow::Owned(value) => value,
+            LocalAnyCow::Shared(value) => value,
+        }
+    }
+
+    /// Returns a mutable reference to the owned data, cloning it first if
+    /// it isn't already owned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::LocalAnyCow;
+    ///
+    /// let items = vec![1, 2];
+    /// let mut cow = LocalAnyCow::borrowed(&items);
+    /// cow.to_mut().push(3);
+    ///
+    /// assert!(cow.is_owned());
+    /// assert_eq!(*cow, vec![1, 2, 3]);
+    /// assert_eq!(items, vec![1, 2]);
+    /// ```
+    pub fn to_mut(&mut self) -> &mut T {
+        // Runs at most twice: the second pass always finds `Owned`.
+        loop {
+            let owned = match self {
+                LocalAnyCow::Owned(value) => return value,
+                _ => self.borrow().to_owned(),
+            };
+            *self = LocalAnyCow::Owned(Box::new(owned));
+        }
+    }
+
+    /// Converts this `LocalAnyCow` into owned data.
+    ///
+    /// A uniquely referenced pointer is unwrapped without cloning.
+    pub fn into_owned(self) -> T {
+        match self {
+            LocalAnyCow::Borrowed(value) => value.to_owned(),
+            LocalAnyCow::Owned(value) => *value,
+            LocalAnyCow::Shared(value) => crate::unwrap_or_to_owned(value),
+        }
+    }
+
+    /// Returns a pointer to the data, cloning it into a new one unless it
+    /// is already shared.
+    pub fn to_shared(&self) -> P {
+        match self {
+            LocalAnyCow::Shared(value) => value.clone(),
+            _ => P::new(self.borrow().to_owned()),
+        }
+    }
+}
+
+impl<T> From<Rc<T>> for LocalAnyCow<'_, T>
+where
+    T: ToOwned<Owned = T>,
+{
+    fn from(value: Rc<T>) -> Self {
+        LocalAnyCow::Shared(value)
+    }
+}
+
+impl<T> From<Arc<T>> for LocalAnyCow<'_, T, Arc<T>>
+where
+    T: ToOwned<Owned = T>,
+{
+    fn from(value: Arc<T>) -> Self {
+        LocalAnyCow::Shared(value)
+    }
+}
+
+impl<'a, T> From<&'a T> for LocalAnyCow<'a, T>
+where
+    T: 'a + ToOwned<Owned = T>,
+{
+    fn from(value: &'a T) -> Self {
+        LocalAnyCow::Borrowed(value)
+    }
+}
+
+/// Cloning a `Shared` value only bumps the `Rc` count; `Owned` values are
+/// cloned deeply.
+impl<'a, T, P> Clone for LocalAnyCow<'a, T, P>
+where
+    T: 'a + ToOwned<Owned = T>,
+    P: RefCount<T>,
+{
+    fn clone(&self) -> Self {
+        match self {
+            LocalAnyCow::Borrowed(value) => LocalAnyCow::Borrowed(value),
+            LocalAnyCow::Owned(value) => LocalAnyCow::Owned(Box::new(value.as_ref().to_owned())),
+            LocalAnyCow::Shared(value) => LocalAnyCow::Shared(value.clone()),
+        }
+    }
+}
+
+impl<'a, T, P> Deref for LocalAnyCow<'a, T, P>
+where
+    T: 'a + ToOwned<Owned = T>,
+    P: RefCount<T>,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.borrow()
+    }
+}
+
+impl<'a, T, P> std::fmt::Debug for LocalAnyCow<'a, T, P>
+where
+    T: 'a + ToOwned<Owned = T> + std::fmt::Debug,
+    P: RefCount<T>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LocalAnyCow::Borrowed(value) => f.debug_tuple("Borrowed").field(value).finish(),
+            LocalAnyCow::Owned(value) => f.debug_tuple("Owned").field(&**value).finish(),
+            LocalAnyCow::Shared(value) => f.debug_tuple("Shared").field(&**value).finish(),
+        }
+    }
+}
+
+impl<'a, T, P> PartialEq for LocalAnyCow<'a, T, P>
+where
+    T: 'a + ToOwned<Owned = T> + PartialEq,
+    P: RefCount<T>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.borrow() == other.borrow()
+    }
+}
+
+impl<'a, T, P> Eq for LocalAnyCow<'a, T, P>
+where
+    T: 'a + ToOwned<Owned = T> + Eq,
+    P: RefCount<T>,
+{
+}
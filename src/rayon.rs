@@ -0,0 +1,39 @@
+//! Parallel transforms backed by `rayon`, enabled by the `rayon` feature.
+
+use crate::AnyCow;
+use rayon::prelude::*;
+
+impl<'a, T> AnyCow<'a, Vec<T>>
+where
+    T: 'a + Clone + Sync,
+{
+    /// Maps every element in parallel on the `rayon` thread pool, producing
+    /// an `Owned` vector.
+    ///
+    /// Element order is preserved. `Lazy` variants are initialized first,
+    /// and `Updatable` variants are mapped from a snapshot of their current
+    /// value, so concurrent replacements don't affect the result. Parallelism
+    /// only pays off for large vectors or expensive transforms; for small
+    /// inputs a plain `iter().map()` is faster.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::AnyCow;
+    ///
+    /// let samples = AnyCow::updatable((0..10_000).collect::<Vec<u64>>());
+    /// let squares = samples.par_map(|x| x * x);
+    ///
+    /// assert!(squares.is_owned());
+    /// assert_eq!(squares.borrow()[100], 10_000);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+    pub fn par_map<U, F>(self, f: F) -> AnyCow<'static, Vec<U>>
+    where
+        U: 'static + Clone + Send,
+        F: Fn(&T) -> U + Sync + Send,
+    {
+        let mapped = self.borrow().par_iter().map(f).collect();
+        AnyCow::Owned(Box::new(mapped))
+    }
+}
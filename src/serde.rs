@@ -0,0 +1,179 @@
+//! `serde` support, enabled by the `serde` feature.
+//!
+//! An `AnyCow` serializes as its current value and deserializes into the
+//! `Owned` variant. Fields that should come out of deserialization as
+//! another variant pick it with `#[serde(with = "...")]`, using
+//! [`as_updatable`] or [`as_shared`].
+
+use crate::AnyCow;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// Serializes the current value, whichever variant holds it.
+///
+/// A `Lazy` variant is initialized first.
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'a, T> Serialize for AnyCow<'a, T>
+where
+    T: 'a + ToOwned<Owned = T> + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.borrow().serialize(serializer)
+    }
+}
+
+/// Deserializes into the `Owned` variant.
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de, 'a, T> Deserialize<'de> for AnyCow<'a, T>
+where
+    T: 'a + ToOwned<Owned = T> + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(AnyCow::owned)
+    }
+}
+
+/// Deserializes an `AnyCow` field into the `Updatable` variant.
+///
+/// Use it with `#[serde(with = "anycow::as_updatable")]` on configuration
+/// that is hot-reloaded later. Serialization is the same as for any
+/// `AnyCow`.
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::AnyCow;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     #[serde(with = "anycow::as_updatable")]
+///     log_level: AnyCow<'static, String>,
+/// }
+///
+/// let config: Config = serde_json::from_str(r#"{ "log_level": "info" }"#).unwrap();
+/// assert!(config.log_level.is_updatable());
+/// config.log_level.try_replace(String::from("debug")).unwrap();
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod as_updatable {
+    use super::*;
+
+    /// Serializes the current value of `cow`.
+    pub fn serialize<T, S>(cow: &AnyCow<'_, T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ToOwned<Owned = T> + Serialize,
+        S: Serializer,
+    {
+        cow.serialize(serializer)
+    }
+
+    /// Deserializes a `T` into an `Updatable` `AnyCow`.
+    pub fn deserialize<'de, 'a, T, D>(deserializer: D) -> Result<AnyCow<'a, T>, D::Error>
+    where
+        T: 'a + ToOwned<Owned = T> + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(AnyCow::updatable)
+    }
+}
+
+/// Deserializes an `AnyCow` field into the `Shared` variant.
+///
+/// Use it with `#[serde(with = "anycow::as_shared")]` on data that will be
+/// handed to other threads as an `Arc` right away.
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod as_shared {
+    use super::*;
+
+    /// Serializes the current value of `cow`.
+    pub fn serialize<T, S>(cow: &AnyCow<'_, T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ToOwned<Owned = T> + Serialize,
+        S: Serializer,
+    {
+        cow.serialize(serializer)
+    }
+
+    /// Deserializes a `T` into a `Shared` `AnyCow`.
+    pub fn deserialize<'de, 'a, T, D>(deserializer: D) -> Result<AnyCow<'a, T>, D::Error>
+    where
+        T: 'a + ToOwned<Owned = T> + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(|value| AnyCow::shared(Arc::new(value)))
+    }
+}
+
+/// An `AnyCow` that deserializes into the `Updatable` variant.
+///
+/// The newtype counterpart to [`as_updatable`], for places where a field
+/// attribute can't reach, such as the elements of a `Vec` or the values of
+/// a `HashMap`.
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::AsUpdatable;
+/// use std::collections::HashMap;
+///
+/// let flags: HashMap<String, AsUpdatable<bool>> =
+///     serde_json::from_str(r#"{ "beta": true }"#).unwrap();
+///
+/// let beta = &flags["beta"];
+/// assert!(beta.is_updatable());
+/// beta.try_replace(false).unwrap();
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub struct AsUpdatable<T>(pub AnyCow<'static, T>)
+where
+    T: 'static + ToOwned<Owned = T>;
+
+impl<T> AsUpdatable<T>
+where
+    T: 'static + ToOwned<Owned = T>,
+{
+    /// Unwraps the `AnyCow`.
+    pub fn into_inner(self) -> AnyCow<'static, T> {
+        self.0
+    }
+}
+
+impl<T> Deref for AsUpdatable<T>
+where
+    T: 'static + ToOwned<Owned = T>,
+{
+    type Target = AnyCow<'static, T>;
+
+    fn deref(&self) -> &AnyCow<'static, T> {
+        &self.0
+    }
+}
+
+impl<T> Serialize for AsUpdatable<T>
+where
+    T: 'static + ToOwned<Owned = T> + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for AsUpdatable<T>
+where
+    T: 'static + ToOwned<Owned = T> + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        as_updatable::deserialize(deserializer).map(AsUpdatable)
+    }
+}
+
+impl<T> std::fmt::Debug for AsUpdatable<T>
+where
+    T: 'static + ToOwned<Owned = T> + std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("AsUpdatable").field(&self.0).finish()
+    }
+}
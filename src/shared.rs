@@ -0,0 +1,222 @@
+//! Shared-only container that supports unsized payloads such as trait objects.
+
+use arc_swap::{ArcSwap, Guard};
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// The shared subset of [`AnyCow`](crate::AnyCow) for unsized payloads,
+/// most notably trait objects.
+///
+/// `AnyCow` needs `T: Sized` and `T: ToOwned<Owned = T>`, which rules out
+/// `dyn Trait`. `SharedAnyCow` drops both requirements by only offering the
+/// variants that never clone the payload: `Shared` holds an `Arc<T>`, and
+/// `Updatable` holds an atomically replaceable `Arc<T>`. This makes it
+/// possible to hot-swap an implementation at runtime, e.g. a logging
+/// backend, while readers keep using whichever implementation they loaded.
+///
+/// `arc-swap` can only store thin pointers, so the `Updatable` variant
+/// keeps the `Arc<T>` in an extra `Arc`, costing one more allocation per
+/// replacement.
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::SharedAnyCow;
+/// use std::sync::Arc;
+///
+/// trait Greeter: Send + Sync {
+///     fn greet(&self) -> String;
+/// }
+///
+/// struct English;
+/// impl Greeter for English {
+///     fn greet(&self) -> String {
+///         String::from("hello")
+///     }
+/// }
+///
+/// struct French;
+/// impl Greeter for French {
+///     fn greet(&self) -> String {
+///         String::from("bonjour")
+///     }
+/// }
+///
+/// let greeter: SharedAnyCow<dyn Greeter> = SharedAnyCow::updatable(Arc::new(English));
+/// assert_eq!(greeter.borrow().greet(), "hello");
+///
+/// assert!(greeter.try_replace(Arc::new(French)).is_ok());
+/// assert_eq!(greeter.borrow().greet(), "bonjour");
+/// ```
+pub enum SharedAnyCow<T>
+where
+    T: ?Sized,
+{
+    /// Reference-counted shared data via `Arc<T>`.
+    Shared(Arc<T>),
+
+    /// An `Arc<T>` that can be replaced atomically using lock-free operations.
+    Updatable(ArcSwap<Arc<T>>),
+}
+
+impl<T> SharedAnyCow<T>
+where
+    T: ?Sized,
+{
+    /// Creates a new `SharedAnyCow` from an `Arc<T>`.
+    pub const fn shared(value: Arc<T>) -> Self {
+        SharedAnyCow::Shared(value)
+    }
+
+    /// Creates a new atomically updatable `SharedAnyCow`.
+    pub fn updatable(value: Arc<T>) -> Self {
+        SharedAnyCow::Updatable(ArcSwap::from_pointee(value))
+    }
+
+    /// Returns `true` if the data is shared through a plain `Arc`.
+    pub const fn is_shared(&self) -> bool {
+        matches!(self, SharedAnyCow::Shared(_))
+    }
+
+    /// Returns `true` if the data can be replaced atomically.
+    pub const fn is_updatable(&self) -> bool {
+        matches!(self, SharedAnyCow::Updatable(_))
+    }
+
+    /// Returns a reference to the contained data.
+    ///
+    /// For the `Updatable` variant the returned [`SharedRef`] holds a guard,
+    /// so the value stays alive even if it is replaced meanwhile.
+    pub fn borrow(&self) -> SharedRef<'_, T> {
+        match self {
+            SharedAnyCow::Shared(value) => SharedRef::Direct(value),
+            SharedAnyCow::Updatable(value) => SharedRef::Guarded(value.load()),
+        }
+    }
+
+    /// Attempts to atomically replace the value.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the replacement was successful
+    /// - `Err(new_val)` if this container is not the `Updatable` variant
+    pub fn try_replace(&self, new_val: Arc<T>) -> Result<(), Arc<T>> {
+        match self {
+            SharedAnyCow::Updatable(value) => {
+                value.store(Arc::new(new_val));
+                Ok(())
+            }
+            SharedAnyCow::Shared(_) => Err(new_val),
+        }
+    }
+
+    /// Returns an `Arc<T>` to the current value.
+    ///
+    /// This never clones the payload, only the `Arc`.
+    pub fn to_arc(&self) -> Arc<T> {
+        match self {
+            SharedAnyCow::Shared(value) => value.clone(),
+            SharedAnyCow::Updatable(value) => Arc::clone(&value.load()),
+        }
+    }
+}
+
+/// Shared slices, the unsized counterpart of `AnyCow<Vec<T>>`.
+impl<T> SharedAnyCow<[T]> {
+    /// Collects `iter` straight into a `Shared` `Arc<[T]>`.
+    ///
+    /// `AnyCow` can't hold a `[T]`, so a large immutable array shared as an
+    /// `AnyCow<Vec<T>>` sits behind two pointers, with the `Vec`'s buffer in
+    /// a second allocation. Collecting into an `Arc<[T]>` stores the
+    /// elements inline in the `Arc`; when the iterator reports its exact
+    /// length (ranges, `map` over a slice, ...) that is the only
+    /// allocation, otherwise the elements pass through a temporary `Vec`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::SharedAnyCow;
+    ///
+    /// let squares = SharedAnyCow::shared_from_iter((0..1000u64).map(|n| n * n));
+    /// assert!(squares.is_shared());
+    /// assert_eq!(squares.borrow()[999], 998_001);
+    /// ```
+    pub fn shared_from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        SharedAnyCow::Shared(iter.into_iter().collect())
+    }
+}
+
+/// Collects into a `Shared` slice, see [`SharedAnyCow::shared_from_iter()`].
+impl<T> FromIterator<T> for SharedAnyCow<[T]> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        SharedAnyCow::shared_from_iter(iter)
+    }
+}
+
+impl<T> From<Arc<T>> for SharedAnyCow<T>
+where
+    T: ?Sized,
+{
+    fn from(value: Arc<T>) -> Self {
+        SharedAnyCow::Shared(value)
+    }
+}
+
+/// Like [`AnyCow`](crate::AnyCow), cloning an `Updatable` produces an
+/// independent `Updatable` starting from the current value.
+impl<T> Clone for SharedAnyCow<T>
+where
+    T: ?Sized,
+{
+    fn clone(&self) -> Self {
+        match self {
+            SharedAnyCow::Shared(value) => SharedAnyCow::Shared(value.clone()),
+            SharedAnyCow::Updatable(value) => {
+                SharedAnyCow::Updatable(ArcSwap::new(value.load_full()))
+            }
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for SharedAnyCow<T>
+where
+    T: ?Sized + std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SharedAnyCow::Shared(value) => f.debug_tuple("Shared").field(&&**value).finish(),
+            SharedAnyCow::Updatable(value) => {
+                f.debug_tuple("Updatable").field(&&**value.load()).finish()
+            }
+        }
+    }
+}
+
+/// A reference to data in a [`SharedAnyCow`].
+pub enum SharedRef<'a, T>
+where
+    T: ?Sized,
+{
+    /// A direct reference to the data of the `Shared` variant.
+    Direct(&'a T),
+
+    /// A guarded reference to the data of the `Updatable` variant.
+    Guarded(Guard<Arc<Arc<T>>>),
+}
+
+impl<T> Deref for SharedRef<'_, T>
+where
+    T: ?Sized,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self {
+            SharedRef::Direct(value) => value,
+            SharedRef::Guarded(guard) => guard.as_ref(),
+        }
+    }
+}
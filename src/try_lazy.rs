@@ -0,0 +1,217 @@
+//! Lazily initialized, atomically updatable values with a fallible initializer.
+
+use crate::{AnyCowRef, AnyCowReplaceError, UpdatableCell};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// What a [`TryLazy`] does after its initializer fails.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum InitPolicy {
+    /// Remember the error: every later access returns it without running
+    /// the initializer again.
+    Cache,
+    /// Forget the error: the next access runs the initializer again, until
+    /// it succeeds once.
+    Retry,
+}
+
+/// The fallible counterpart to [`AnyCow::lazy()`](crate::AnyCow::lazy).
+///
+/// The initializer returns a `Result`; what happens after an `Err` is
+/// chosen up front with an [`InitPolicy`]: [`cached()`](Self::cached)
+/// fails fast by remembering the first error, which suits initializers
+/// that can't succeed later (parsing a constant), while
+/// [`retry()`](Self::retry) runs the initializer again on the next access,
+/// which suits transient failures (network fetches).
+///
+/// Initialization is serialized, so the initializer never runs
+/// concurrently with itself. A panicking initializer leaves the value
+/// uninitialized; it doesn't poison it, and the next access tries again
+/// under either policy. Once initialized, the value can be replaced
+/// atomically just like a `Lazy` `AnyCow`.
+///
+/// Like `AnyCow::lazy()`, the constructors are `const fn` when given a
+/// function pointer, so a `TryLazy` can be a `static`.
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::TryLazy;
+///
+/// fn parse_port() -> Result<u16, String> {
+///     "8080".parse().map_err(|_| String::from("invalid port"))
+/// }
+///
+/// static PORT: TryLazy<u16, String> = TryLazy::cached(parse_port);
+///
+/// assert_eq!(*PORT.get().unwrap(), 8080);
+/// ```
+pub struct TryLazy<T, E, F = fn() -> Result<T, E>> {
+    data: OnceLock<UpdatableCell<T>>,
+    error: OnceLock<E>,
+    init_lock: Mutex<()>,
+    init: F,
+    policy: InitPolicy,
+}
+
+impl<T, E, F> TryLazy<T, E, F>
+where
+    T: ToOwned<Owned = T>,
+    F: Fn() -> Result<T, E>,
+{
+    /// Creates a `TryLazy` that caches the first initialization error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::TryLazy;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// let calls = AtomicUsize::new(0);
+    /// let value = TryLazy::cached(|| {
+    ///     calls.fetch_add(1, Ordering::SeqCst);
+    ///     Err::<i32, _>("unreachable host")
+    /// });
+    ///
+    /// assert_eq!(value.get().err(), Some("unreachable host"));
+    /// assert_eq!(value.get().err(), Some("unreachable host"));
+    /// assert_eq!(calls.load(Ordering::SeqCst), 1);
+    /// ```
+    #[doc(alias = "try_lazy_cached")]
+    pub const fn cached(init: F) -> Self {
+        Self::with_policy(init, InitPolicy::Cache)
+    }
+
+    /// Creates a `TryLazy` that runs the initializer again after a failure.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anycow::TryLazy;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// let attempts = AtomicUsize::new(0);
+    /// let value = TryLazy::retry(|| match attempts.fetch_add(1, Ordering::SeqCst) {
+    ///     0 => Err("timeout"),
+    ///     n => Ok(n),
+    /// });
+    ///
+    /// assert_eq!(value.get().err(), Some("timeout"));
+    /// assert_eq!(*value.get().unwrap(), 1);
+    /// // Initialized now, the initializer doesn't run again
+    /// assert_eq!(*value.get().unwrap(), 1);
+    /// ```
+    #[doc(alias = "try_lazy_retry")]
+    pub const fn retry(init: F) -> Self {
+        Self::with_policy(init, InitPolicy::Retry)
+    }
+
+    /// Creates a `TryLazy` with an explicit [`InitPolicy`].
+    pub const fn with_policy(init: F, policy: InitPolicy) -> Self {
+        TryLazy {
+            data: OnceLock::new(),
+            error: OnceLock::new(),
+            init_lock: Mutex::new(()),
+            init,
+            policy,
+        }
+    }
+
+    /// Returns the policy applied when the initializer fails.
+    pub const fn policy(&self) -> InitPolicy {
+        self.policy
+    }
+
+    /// Returns the value, running the initializer first if needed.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(value)` once initialization has succeeded
+    /// - `Err(error)` if the initializer failed on this access or, with
+    ///   [`InitPolicy::Cache`], on an earlier one
+    pub fn get(&self) -> Result<AnyCowRef<'_, T>, E>
+    where
+        E: Clone,
+    {
+        self.cell().map(|cell| AnyCowRef::Guarded(cell.load()))
+    }
+
+    /// Returns `true` if the initializer has succeeded.
+    pub fn is_initialized(&self) -> bool {
+        self.data.get().is_some()
+    }
+
+    /// Returns the cached initialization error, if any.
+    ///
+    /// Always `None` with [`InitPolicy::Retry`].
+    pub fn error(&self) -> Option<&E> {
+        self.error.get()
+    }
+
+    /// Returns a full `Arc` to the value, running the initializer first if
+    /// needed.
+    pub fn to_arc(&self) -> Result<Arc<T>, E>
+    where
+        E: Clone,
+    {
+        self.cell().map(UpdatableCell::load_full)
+    }
+
+    /// Atomically replaces the value.
+    ///
+    /// Replacing works only after a successful initialization; it never
+    /// runs the initializer. This keeps a failed `TryLazy` from being
+    /// silently repaired, use [`get()`](Self::get) to initialize first.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the replacement was successful
+    /// - `Err(AnyCowReplaceError)` if the value isn't initialized
+    pub fn try_replace(&self, new_val: T) -> Result<(), AnyCowReplaceError> {
+        let cell = self.data.get().ok_or(AnyCowReplaceError)?;
+        cell.store(Arc::new(new_val))
+            .map_err(|_| AnyCowReplaceError)
+    }
+
+    fn cell(&self) -> Result<&UpdatableCell<T>, E>
+    where
+        E: Clone,
+    {
+        if let Some(cell) = self.data.get() {
+            return Ok(cell);
+        }
+        // A panicking initializer poisons the lock, but leaves no partial
+        // state behind, so the poison can be ignored.
+        let _guard = self.init_lock.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(cell) = self.data.get() {
+            return Ok(cell);
+        }
+        if let Some(error) = self.error.get() {
+            return Err(error.clone());
+        }
+        match (self.init)() {
+            Ok(value) => Ok(self.data.get_or_init(|| UpdatableCell::new(value))),
+            Err(error) => {
+                if self.policy == InitPolicy::Cache {
+                    let _ = self.error.set(error.clone());
+                }
+                Err(error)
+            }
+        }
+    }
+}
+
+impl<T, E, F> std::fmt::Debug for TryLazy<T, E, F>
+where
+    T: std::fmt::Debug,
+    E: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut tuple = f.debug_tuple("TryLazy");
+        match (self.data.get(), self.error.get()) {
+            (Some(cell), _) => tuple.field(&Ok::<_, ()>(&*cell.load())),
+            (None, Some(error)) => tuple.field(&Err::<(), _>(error)),
+            (None, None) => tuple.field(&"<uninitialized>"),
+        };
+        tuple.finish()
+    }
+}
@@ -0,0 +1,1341 @@
+//! Atomic storage backing the `Tracked` variant, and the handle shared by
+//! every updatable variant.
+
+use crate::Rejected;
+use arc_swap::{ArcSwap, Cache, Guard};
+use std::borrow::Borrow;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::sync::atomic::{self, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Lock-free, atomically updatable storage used by [`AnyCow::Tracked`].
+///
+/// `UpdatableCell` wraps an `ArcSwap<T>` and routes every write through a
+/// single path, so optional features such as the update history kept by
+/// [`AnyCow::updatable_with_history()`] observe each replacement. Plain
+/// [`AnyCow::Updatable`] and [`AnyCow::Lazy`] values hold a bare `ArcSwap`
+/// and pay for none of this.
+///
+/// [`AnyCow::Tracked`]: crate::AnyCow::Tracked
+/// [`AnyCow::Updatable`]: crate::AnyCow::Updatable
+/// [`AnyCow::Lazy`]: crate::AnyCow::Lazy
+/// [`AnyCow::updatable_with_history()`]: crate::AnyCow::updatable_with_history
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::UpdatableCell;
+/// use std::sync::Arc;
+///
+/// let cell = UpdatableCell::new(1);
+/// cell.store(Arc::new(2));
+/// assert_eq!(**cell.load(), 2);
+/// ```
+pub struct UpdatableCell<T> {
+    storage: Storage<T>,
+    history: Option<Box<History<T>>>,
+    refresh: Option<Box<Refresh<T>>>,
+    validator: Option<Box<Validator<T>>>,
+    debounce: Option<Box<Debounce>>,
+    #[cfg(feature = "crossbeam")]
+    sender: Option<Box<crossbeam_channel::Sender<Arc<T>>>>,
+    notifier: OnceLock<Notifier>,
+}
+
+/// Checks replacement values, see [`UpdatableCell::validated()`].
+type Validator<T> = dyn Fn(&T) -> Result<(), Box<dyn Error + Send + Sync>> + Send + Sync;
+
+/// The data touched on every read and write. `V` is the `ArcSwap<T>`
+/// itself or a shared handle to one.
+struct Slot<V> {
+    value: V,
+    generation: AtomicU64,
+    /// Outside references to the value replaced by the last store.
+    retained: AtomicUsize,
+}
+
+enum Storage<T> {
+    Inline(Slot<ArcSwap<T>>),
+    /// Kept in its own allocation aligned to a cache line, see
+    /// [`UpdatableCell::padded()`].
+    Padded(Box<CachePadded<Slot<ArcSwap<T>>>>),
+    /// An `ArcSwap` owned elsewhere, see [`UpdatableCell::from_arc_swap()`].
+    Adopted(Slot<Arc<ArcSwap<T>>>),
+    /// Created on first access, see [`UpdatableCell::lazy()`].
+    Lazy(Box<LazySlot<T>>),
+}
+
+/// A slot that runs `init` the first time it is needed.
+struct LazySlot<T> {
+    slot: OnceLock<Slot<ArcSwap<T>>>,
+    init: Box<dyn Fn() -> T + Send + Sync>,
+}
+
+/// Aligns its contents to the cache line size, so nothing else shares
+/// their cache line. 128 bytes on targets that prefetch cache lines in
+/// pairs, 64 bytes elsewhere.
+#[cfg_attr(any(target_arch = "x86_64", target_arch = "aarch64"), repr(align(128)))]
+#[cfg_attr(
+    not(any(target_arch = "x86_64", target_arch = "aarch64")),
+    repr(align(64))
+)]
+struct CachePadded<T>(T);
+
+/// Ring buffer of previously stored snapshots.
+struct History<T> {
+    capacity: usize,
+    snapshots: Mutex<VecDeque<Arc<T>>>,
+}
+
+/// Reloads the value on read once it's older than `ttl`.
+struct Refresh<T> {
+    /// Maximum age of the value in nanoseconds.
+    ttl: AtomicU64,
+    /// Reference point for `refreshed_at`.
+    epoch: Instant,
+    /// Nanoseconds since `epoch` at the last refresh.
+    refreshed_at: AtomicU64,
+    /// Held by the one thread that is currently refreshing.
+    lock: Mutex<()>,
+    reload: Box<dyn Fn() -> T + Send + Sync>,
+}
+
+/// An interval started by [`UpdatableCell::admit()`].
+#[derive(Default)]
+struct Admission {
+    /// The end of the interval before this one.
+    previous: u64,
+    /// The end of this interval.
+    claimed: u64,
+}
+
+/// Drops replacements that arrive too soon after the last one.
+struct Debounce {
+    /// Minimum time between applied replacements in nanoseconds.
+    min_interval: u64,
+    /// Reference point for `next`.
+    epoch: Instant,
+    /// Nanoseconds since `epoch` before which replacements are dropped.
+    next: AtomicU64,
+}
+
+/// Wakes blocked [`Watcher`]s and, with the `tokio` feature, pending
+/// `changed()` futures. Only set up once something watches the cell.
+#[derive(Default)]
+struct Notifier {
+    lock: Mutex<()>,
+    changed: Condvar,
+    #[cfg(feature = "tokio")]
+    notify: tokio::sync::Notify,
+}
+
+/// Wakes watchers of plain `Updatable` and `Lazy` values, which have no
+/// cell to keep a [`Notifier`] in. It is shared by all of them, so a
+/// watcher may wake for a write to another container and then goes back
+/// to waiting.
+static PLAIN_NOTIFIER: OnceLock<Notifier> = OnceLock::new();
+
+/// The number of live watchers of plain values. Writes only take the
+/// [`PLAIN_NOTIFIER`] lock while it is non-zero.
+static PLAIN_WATCHERS: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts as one of the [`PLAIN_WATCHERS`] while alive.
+struct PlainWatch;
+
+impl PlainWatch {
+    /// Registers a watcher. Must happen before the watcher first reads the
+    /// value, see [`published_plain()`].
+    fn start() -> Self {
+        PLAIN_WATCHERS.fetch_add(1, Ordering::SeqCst);
+        atomic::fence(Ordering::SeqCst);
+        PlainWatch
+    }
+}
+
+impl Drop for PlainWatch {
+    fn drop(&mut self) {
+        PLAIN_WATCHERS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Wakes watchers of plain values after a store to one.
+fn published_plain() {
+    // Pairs with the fence in `PlainWatch::start()`: either the watcher
+    // reads the new value, or this write sees the watcher.
+    atomic::fence(Ordering::SeqCst);
+    if PLAIN_WATCHERS.load(Ordering::SeqCst) > 0 {
+        PLAIN_NOTIFIER.get_or_init(Notifier::default).wake();
+    }
+}
+
+impl<T> UpdatableCell<T> {
+    /// Creates a new cell holding `value`.
+    pub fn new(value: T) -> Self {
+        Self::from_arc(Arc::new(value))
+    }
+
+    /// Creates a new cell holding an existing `Arc<T>` without cloning it.
+    pub fn from_arc(value: Arc<T>) -> Self {
+        Self::with_storage(Storage::Inline(Slot::new(ArcSwap::from(value))))
+    }
+
+    /// Returns a [`CellBuilder`] for a cell holding `value` that combines
+    /// several of the optional policies below.
+    pub fn builder(value: T) -> CellBuilder<T> {
+        CellBuilder {
+            value,
+            padded: false,
+            history: None,
+            refresh: None,
+            validator: None,
+            debounce: None,
+            #[cfg(feature = "crossbeam")]
+            sender: None,
+        }
+    }
+
+    fn with_storage(storage: Storage<T>) -> Self {
+        UpdatableCell {
+            storage,
+            history: None,
+            refresh: None,
+            validator: None,
+            debounce: None,
+            #[cfg(feature = "crossbeam")]
+            sender: None,
+            notifier: OnceLock::new(),
+        }
+    }
+
+    /// Creates a new cell whose atomic pointer lives on its own cache line.
+    ///
+    /// Cells that sit next to each other, e.g. in an array or a struct of
+    /// counters, otherwise share cache lines, so a write to one slows down
+    /// reads of its neighbours on other cores (false sharing). Padding costs
+    /// an extra allocation of one cache line per cell.
+    pub fn padded(value: T) -> Self {
+        Self::builder(value).padded().build()
+    }
+
+    /// Creates a new cell that keeps up to `capacity` previous values.
+    ///
+    /// Every store pushes the value it replaces into a ring buffer;
+    /// once the buffer is full the oldest snapshot is dropped.
+    pub fn with_history(value: T, capacity: usize) -> Self {
+        Self::builder(value).history(capacity).build()
+    }
+
+    /// Creates a cell that calls `reload` to replace a value older than `ttl`.
+    ///
+    /// The age is checked on every [`load()`](Self::load) and
+    /// [`load_full()`](Self::load_full). Once it exceeds `ttl`, the first
+    /// reader to notice runs `reload` and stores the result; readers that
+    /// arrive while the reload is in progress don't wait for it and get the
+    /// stale value instead. Values stored by other means don't reset the
+    /// age.
+    pub fn with_refresh<F>(value: T, ttl: Duration, reload: F) -> Self
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        Self::builder(value).refresh(ttl, reload).build()
+    }
+
+    /// Changes the maximum age of a cell created by
+    /// [`with_refresh()`](Self::with_refresh); `Duration::MAX` turns
+    /// refreshing off.
+    ///
+    /// Returns `false` for cells without a reload function.
+    pub fn set_refresh(&self, ttl: Duration) -> bool {
+        match &self.refresh {
+            Some(refresh) => {
+                refresh.ttl.store(nanos(ttl), Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Creates a cell that applies at most one replacement per
+    /// `min_interval`.
+    ///
+    /// Every write is checked against the interval right before it is
+    /// published, after validation. A write that arrives too soon is
+    /// dropped but still reports success. The first replacement is always
+    /// applied.
+    pub fn debounced(value: T, min_interval: Duration) -> Self {
+        Self::builder(value).debounce(min_interval).build()
+    }
+
+    /// Returns whether a replacement may be applied now, and if so starts
+    /// a new interval of a cell created by [`debounced()`](Self::debounced).
+    ///
+    /// Of several threads calling this at once, at most one is admitted.
+    /// Always admits for cells without a minimum interval. A writer that
+    /// is admitted but then doesn't publish hands the interval back with
+    /// [`revoke()`](Self::revoke).
+    fn admit(&self) -> Option<Admission> {
+        let Some(debounce) = &self.debounce else {
+            return Some(Admission::default());
+        };
+        let now = nanos(debounce.epoch.elapsed());
+        let previous = debounce.next.load(Ordering::Relaxed);
+        let claimed = now.saturating_add(debounce.min_interval);
+        let admitted = now >= previous
+            && debounce
+                .next
+                .compare_exchange(previous, claimed, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok();
+        admitted.then_some(Admission { previous, claimed })
+    }
+
+    /// Ends the interval started by an admitted write that didn't publish,
+    /// so the next write isn't dropped in its place.
+    fn revoke(&self, admission: Admission) {
+        if let Some(debounce) = &self.debounce {
+            // Fails, leaving it alone, if another write has started a
+            // newer interval since.
+            let _ = debounce.next.compare_exchange(
+                admission.claimed,
+                admission.previous,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            );
+        }
+    }
+
+    /// Creates a cell whose replacements are checked by `validator`.
+    ///
+    /// Every write runs the validator before publishing, whether it comes
+    /// through [`store()`](Self::store), [`swap()`](Self::swap) or any
+    /// method of [`AnyCow`](crate::AnyCow), and a rejected value is handed
+    /// back without being stored. `value` itself is assumed to be valid
+    /// and isn't checked.
+    pub fn validated<V, E>(value: T, validator: V) -> Self
+    where
+        V: Fn(&T) -> Result<(), E> + Send + Sync + 'static,
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        Self::builder(value).validator(validator).build()
+    }
+
+    /// Creates a cell that sends every newly stored value to the returned
+    /// receiver.
+    ///
+    /// The channel is unbounded, so stores never block, but values pile
+    /// up in it while nobody receives them. Once the receiver (and all
+    /// its clones) is dropped, stores carry on and the values are
+    /// discarded. Concurrent stores may be sent in a different order than
+    /// they took effect.
+    #[cfg(feature = "crossbeam")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "crossbeam")))]
+    pub fn with_channel(value: T) -> (Self, crossbeam_channel::Receiver<Arc<T>>) {
+        let (builder, receiver) = Self::builder(value).channel();
+        (builder.build(), receiver)
+    }
+
+    /// Runs the validator of a cell created by
+    /// [`validated()`](Self::validated) against `value`.
+    ///
+    /// Always succeeds for cells without a validator.
+    pub fn validate(&self, value: &T) -> Result<(), Box<dyn Error + Send + Sync>> {
+        match &self.validator {
+            Some(validator) => validator(value),
+            None => Ok(()),
+        }
+    }
+
+    /// Hands `value` back as [`Rejected`] if the validator refuses it.
+    fn check<V>(&self, value: V) -> Result<V, Rejected<V>>
+    where
+        V: Borrow<T>,
+    {
+        match self.validate(value.borrow()) {
+            Ok(()) => Ok(value),
+            Err(reason) => Err(Rejected {
+                value,
+                reason: Some(reason),
+            }),
+        }
+    }
+
+    /// Creates a cell whose value is computed by `init` on first access.
+    ///
+    /// `init` runs at most once, even if several threads touch the cell
+    /// for the first time concurrently; the others wait for it. Unlike
+    /// the `fn() -> T` of [`AnyCow::Lazy`](crate::AnyCow::Lazy), `init` may
+    /// capture state.
+    pub fn lazy<F>(init: F) -> Self
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        Self::with_storage(Storage::Lazy(Box::new(LazySlot {
+            slot: OnceLock::new(),
+            init: Box::new(init),
+        })))
+    }
+
+    /// Returns `false` for a cell created by [`lazy()`](Self::lazy) whose
+    /// initializer hasn't run yet.
+    pub fn is_initialized(&self) -> bool {
+        match &self.storage {
+            Storage::Lazy(lazy) => lazy.slot.get().is_some(),
+            _ => true,
+        }
+    }
+
+    /// Returns the heap bytes held by the cell's own boxes: padded or lazy
+    /// storage and the history, refresh, validator, debounce and channel
+    /// policies. The value and the cell itself aren't included.
+    pub(crate) fn overhead(&self) -> usize {
+        use std::mem::{size_of, size_of_val};
+
+        let storage = match &self.storage {
+            Storage::Padded(_) => size_of::<CachePadded<Slot<ArcSwap<T>>>>(),
+            Storage::Lazy(lazy) => size_of::<LazySlot<T>>() + size_of_val(&*lazy.init),
+            Storage::Inline(_) | Storage::Adopted(_) => 0,
+        };
+        let history = self.history.as_ref().map_or(0, |history| {
+            size_of::<History<T>>() + history.lock().capacity() * size_of::<Arc<T>>()
+        });
+        let refresh = self.refresh.as_ref().map_or(0, |refresh| {
+            size_of::<Refresh<T>>() + size_of_val(&*refresh.reload)
+        });
+        let validator = self.validator.as_ref().map_or(0, |v| size_of_val(&**v));
+        let debounce = self.debounce.as_ref().map_or(0, |_| size_of::<Debounce>());
+        #[cfg(feature = "crossbeam")]
+        let channel = self
+            .sender
+            .as_ref()
+            .map_or(0, |_| size_of::<crossbeam_channel::Sender<Arc<T>>>());
+        #[cfg(not(feature = "crossbeam"))]
+        let channel = 0;
+        storage + history + refresh + validator + debounce + channel
+    }
+
+    /// Initializes a cell created by [`lazy()`](Self::lazy) with the result
+    /// of `f` instead of its own initializer, unless it's initialized
+    /// already. Does nothing for other cells.
+    pub(crate) fn init_with<F>(&self, f: F)
+    where
+        F: FnOnce() -> T,
+    {
+        if let Storage::Lazy(lazy) = &self.storage {
+            lazy.slot
+                .get_or_init(|| Slot::new(ArcSwap::from_pointee(f())));
+        }
+    }
+
+    /// Makes `value` the first value of an uninitialized cell created by
+    /// [`lazy()`](Self::lazy), handing it back for every other cell.
+    pub(crate) fn set(&self, value: T) -> Result<(), T> {
+        let mut value = Some(value);
+        self.init_with(|| value.take().expect("initializer runs at most once"));
+        match value {
+            Some(value) => Err(value),
+            None => Ok(()),
+        }
+    }
+
+    /// Creates a cell backed by an `ArcSwap` that is shared with other code.
+    ///
+    /// Loads and stores go straight to `value`, so every cell (and any other
+    /// code) using the same `ArcSwap` sees the others' writes. History and
+    /// [`watch()`](Self::watch) only observe writes made through this
+    /// particular cell, though.
+    pub fn from_arc_swap(value: Arc<ArcSwap<T>>) -> Self {
+        Self::with_storage(Storage::Adopted(Slot::new(value)))
+    }
+
+    fn value(&self) -> &ArcSwap<T> {
+        match &self.storage {
+            Storage::Inline(slot) => &slot.value,
+            Storage::Padded(padded) => &padded.0.value,
+            Storage::Adopted(slot) => &slot.value,
+            Storage::Lazy(lazy) => &lazy.slot().value,
+        }
+    }
+
+    fn generation(&self) -> &AtomicU64 {
+        match &self.storage {
+            Storage::Inline(slot) => &slot.generation,
+            Storage::Padded(padded) => &padded.0.generation,
+            Storage::Adopted(slot) => &slot.generation,
+            Storage::Lazy(lazy) => &lazy.slot().generation,
+        }
+    }
+
+    fn retained(&self) -> &AtomicUsize {
+        match &self.storage {
+            Storage::Inline(slot) => &slot.retained,
+            Storage::Padded(padded) => &padded.0.retained,
+            Storage::Adopted(slot) => &slot.retained,
+            Storage::Lazy(lazy) => &lazy.slot().retained,
+        }
+    }
+
+    /// Returns how many references outside the cell still held the
+    /// previous value at the moment it was last replaced, or 0 before the
+    /// first store.
+    ///
+    /// Those references are guards from [`load()`](Self::load) and
+    /// snapshots from [`load_full()`](Self::load_full), on any thread; the
+    /// cell's own history doesn't count. Each of them keeps the old value
+    /// in memory until it is dropped.
+    pub fn retained_count(&self) -> usize {
+        self.retained().load(Ordering::Relaxed)
+    }
+
+    /// Records the outside references to `previous`, not counting `own`
+    /// references held by the cell and the caller.
+    fn record_retained(&self, previous: &Arc<T>, own: usize) {
+        // The swap has turned every guard on `previous` into a counted
+        // reference, so the strong count is exact at this point.
+        let retained = Arc::strong_count(previous).saturating_sub(own);
+        self.retained().store(retained, Ordering::Relaxed);
+    }
+
+    /// Sends a newly stored value to the channel of a
+    /// [`with_channel()`](Self::with_channel) cell.
+    #[cfg(feature = "crossbeam")]
+    fn forward(&self, value: Arc<T>) {
+        if let Some(sender) = &self.sender {
+            // Fails only once every receiver is gone, nobody is listening.
+            let _ = sender.send(value);
+        }
+    }
+
+    /// Returns a guard to the current value.
+    pub fn load(&self) -> Guard<Arc<T>> {
+        self.refresh_if_stale();
+        self.value().load()
+    }
+
+    /// Returns a full `Arc` to the current value.
+    pub fn load_full(&self) -> Arc<T> {
+        self.refresh_if_stale();
+        self.value().load_full()
+    }
+
+    /// Returns the strong count of the current value's `Arc`, including the
+    /// reference held by the cell itself.
+    pub(crate) fn strong_count(&self) -> usize {
+        Arc::strong_count(&self.value().load())
+    }
+
+    /// Runs the reload function of a cell created by
+    /// [`with_refresh()`](Self::with_refresh) if its value has expired.
+    fn refresh_if_stale(&self) {
+        let Some(refresh) = &self.refresh else {
+            return;
+        };
+        if !refresh.is_stale() {
+            return;
+        }
+        // Whoever holds the lock is already refreshing; serve the stale
+        // value rather than queueing up behind it. This also keeps a reload
+        // function that reads this cell from deadlocking.
+        let _guard = match refresh.lock.try_lock() {
+            Ok(guard) => guard,
+            Err(std::sync::TryLockError::Poisoned(e)) => e.into_inner(),
+            Err(std::sync::TryLockError::WouldBlock) => return,
+        };
+        if !refresh.is_stale() {
+            return;
+        }
+        let value = (refresh.reload)();
+        // A reloaded value the validator refuses is dropped; the current
+        // value is served until the next refresh.
+        let _ = self.store(Arc::new(value));
+        refresh.refreshed_at.store(refresh.now(), Ordering::Release);
+    }
+
+    /// Atomically replaces the current value.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the value was stored
+    /// - `Err(rejected)` with the value if the validator of a
+    ///   [`validated()`](Self::validated) cell refused it
+    pub fn store(&self, value: Arc<T>) -> Result<(), Rejected<Arc<T>>> {
+        self.swap(value).map(drop)
+    }
+
+    /// Atomically replaces the current value, returning the previous one.
+    ///
+    /// Fails like [`store()`](Self::store) if the validator refuses `value`.
+    pub fn swap(&self, value: Arc<T>) -> Result<Arc<T>, Rejected<Arc<T>>> {
+        self.swap_counted(value).map(|(previous, _)| previous)
+    }
+
+    /// Replaces the current value, reporting the generations around it.
+    pub(crate) fn store_tracked<V>(&self, value: V) -> Result<ReplaceStats, Rejected<V>>
+    where
+        V: Borrow<T> + Into<Arc<T>>,
+    {
+        let observed_generation = self.generation().load(Ordering::SeqCst);
+        let (_, generation) = self.swap_counted(value)?;
+        Ok(ReplaceStats {
+            observed_generation,
+            generation: generation.unwrap_or(observed_generation),
+        })
+    }
+
+    /// Swaps in `value`, returning the previous value and the generation
+    /// this store produced, or `None` if the minimum interval of a
+    /// [`debounced()`](Self::debounced) cell dropped it. A dropped value
+    /// reports the value still in place as the previous one.
+    ///
+    /// Together with [`compare_and_swap()`](Self::compare_and_swap) this is
+    /// the only place that publishes values, so the validator and the
+    /// minimum interval are applied here.
+    pub(crate) fn swap_counted<V>(&self, value: V) -> Result<(Arc<T>, Option<u64>), Rejected<V>>
+    where
+        V: Borrow<T> + Into<Arc<T>>,
+    {
+        let value: Arc<T> = self.check(value)?.into();
+        if self.admit().is_none() {
+            return Ok((self.value().load_full(), None));
+        }
+        #[cfg(feature = "crossbeam")]
+        let stored = value.clone();
+        let previous = match &self.history {
+            None => self.value().swap(value),
+            Some(history) => {
+                // Hold the lock across the swap so the ring keeps the same
+                // order as the stores themselves.
+                let mut snapshots = history.lock();
+                let previous = self.value().swap(value);
+                history.push(&mut snapshots, previous.clone());
+                previous
+            }
+        };
+        self.record_retained(&previous, 1 + usize::from(self.history.is_some()));
+        let generation = self.published();
+        #[cfg(feature = "crossbeam")]
+        self.forward(stored);
+        Ok((previous, Some(generation)))
+    }
+
+    /// Stores `new` only if the current value is still `current` and the
+    /// validator accepts it.
+    ///
+    /// Values are compared by pointer, so holding `current` also rules out
+    /// ABA problems. Returns the replaced value on success and hands `new`
+    /// back on failure. Like [`swap_counted()`](Self::swap_counted), a
+    /// value dropped by the minimum interval counts as a success, as long
+    /// as `current` is still in place. A failed compare doesn't use up the
+    /// interval.
+    pub(crate) fn compare_and_swap(
+        &self,
+        current: &Arc<T>,
+        new: Arc<T>,
+    ) -> Result<Arc<T>, CasError<T>> {
+        let new = self.check(new).map_err(CasError::Rejected)?;
+        let Some(admission) = self.admit() else {
+            return if Arc::ptr_eq(&self.value().load(), current) {
+                Ok(current.clone())
+            } else {
+                Err(CasError::Changed(new))
+            };
+        };
+        let mut snapshots = self.history.as_ref().map(|history| history.lock());
+        let previous = self.value().compare_and_swap(current, new.clone());
+        if !Arc::ptr_eq(&previous, current) {
+            self.revoke(admission);
+            return Err(CasError::Changed(new));
+        }
+        let previous = Guard::into_inner(previous);
+        if let (Some(history), Some(snapshots)) = (&self.history, snapshots.as_mut()) {
+            history.push(snapshots, previous.clone());
+        }
+        drop(snapshots);
+        // `previous` and the caller's `current` point to the same value.
+        self.record_retained(&previous, 2 + usize::from(self.history.is_some()));
+        self.published();
+        #[cfg(feature = "crossbeam")]
+        self.forward(new);
+        Ok(previous)
+    }
+
+    /// Returns the previously stored values, oldest first.
+    ///
+    /// Always empty unless the cell was created with
+    /// [`with_history()`](Self::with_history).
+    pub fn history(&self) -> Vec<Arc<T>> {
+        match &self.history {
+            Some(history) => history.lock().iter().cloned().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Folds over the previously stored values, oldest first, without
+    /// copying them out.
+    ///
+    /// The history stays locked while `f` runs, so writers wait for it and
+    /// `f` must not store into this cell.
+    pub fn fold_history<B, F>(&self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, &T) -> B,
+    {
+        match &self.history {
+            Some(history) => history.lock().iter().fold(init, |acc, value| f(acc, value)),
+            None => init,
+        }
+    }
+
+    /// Creates a reader that memoizes the current value.
+    ///
+    /// See [`Cached`] for details.
+    pub fn cached(&self) -> Cached<'_, T> {
+        Cached {
+            cache: Cache::new(self.value()),
+        }
+    }
+
+    /// Returns a [`Watcher`] that blocks until the value is replaced.
+    pub fn watch(&self) -> Watcher<'_, T> {
+        // Initialize the notifier before reading the generation, so a store
+        // that misses the notifier is still visible as a newer generation.
+        self.notifier.get_or_init(Notifier::default);
+        Watcher {
+            source: Source::Cell {
+                cell: self,
+                seen: self.generation().load(Ordering::SeqCst),
+            },
+        }
+    }
+
+    /// Returns a future that resolves with the latest value once the value
+    /// has been replaced after this call.
+    ///
+    /// See [`AnyCow::changed()`](crate::AnyCow::changed).
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+    pub fn changed(&self) -> impl std::future::Future<Output = Arc<T>> + '_ {
+        // Read the generation now, so stores between this call and the
+        // first poll count.
+        self.watch().changed()
+    }
+
+    /// Bumps the generation and wakes watchers after every store.
+    ///
+    /// Returns the new generation.
+    fn published(&self) -> u64 {
+        let generation = self.generation().fetch_add(1, Ordering::SeqCst) + 1;
+        if let Some(notifier) = self.notifier.get() {
+            notifier.wake();
+        }
+        generation
+    }
+
+    /// Consumes the cell and returns the current value.
+    pub fn into_inner(self) -> Arc<T> {
+        match self.storage {
+            Storage::Inline(slot) => slot.value.into_inner(),
+            Storage::Padded(padded) => padded.0.value.into_inner(),
+            Storage::Adopted(slot) => slot.value.load_full(),
+            Storage::Lazy(lazy) => {
+                let LazySlot { slot, init } = *lazy;
+                match slot.into_inner() {
+                    Some(slot) => slot.value.into_inner(),
+                    None => Arc::new(init()),
+                }
+            }
+        }
+    }
+}
+
+/// Builds an [`UpdatableCell`] that combines several optional policies.
+///
+/// Created by [`UpdatableCell::builder()`]. Each single-policy constructor,
+/// such as [`UpdatableCell::with_history()`], is a builder with one method
+/// called. A built cell becomes a `Tracked` [`AnyCow`](crate::AnyCow)
+/// through `From`.
+///
+/// # Examples
+///
+/// ```rust
+/// use anycow::{AnyCow, UpdatableCell};
+/// use std::time::Duration;
+///
+/// let workers: AnyCow<u32> = UpdatableCell::builder(4)
+///     .history(8)
+///     .validator(|&n: &u32| if n == 0 { Err("at least one worker") } else { Ok(()) })
+///     .debounce(Duration::from_secs(1))
+///     .build()
+///     .into();
+///
+/// assert!(workers.try_replace(0).is_err());
+/// workers.try_replace(6).unwrap();
+/// assert_eq!(*workers.history()[0], 4);
+/// ```
+pub struct CellBuilder<T> {
+    value: T,
+    padded: bool,
+    history: Option<Box<History<T>>>,
+    refresh: Option<Box<Refresh<T>>>,
+    validator: Option<Box<Validator<T>>>,
+    debounce: Option<Box<Debounce>>,
+    #[cfg(feature = "crossbeam")]
+    sender: Option<Box<crossbeam_channel::Sender<Arc<T>>>>,
+}
+
+impl<T> CellBuilder<T> {
+    /// Puts the atomic pointer on its own cache line, see
+    /// [`UpdatableCell::padded()`].
+    pub fn padded(mut self) -> Self {
+        self.padded = true;
+        self
+    }
+
+    /// Keeps up to `capacity` previous values, see
+    /// [`UpdatableCell::with_history()`].
+    pub fn history(mut self, capacity: usize) -> Self {
+        self.history = Some(Box::new(History {
+            capacity,
+            snapshots: Mutex::new(VecDeque::with_capacity(capacity)),
+        }));
+        self
+    }
+
+    /// Reloads a value older than `ttl` with `reload`, see
+    /// [`UpdatableCell::with_refresh()`].
+    pub fn refresh<F>(mut self, ttl: Duration, reload: F) -> Self
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        self.refresh = Some(Box::new(Refresh {
+            ttl: AtomicU64::new(nanos(ttl)),
+            epoch: Instant::now(),
+            refreshed_at: AtomicU64::new(0),
+            lock: Mutex::new(()),
+            reload: Box::new(reload),
+        }));
+        self
+    }
+
+    /// Checks every replacement with `validator`, see
+    /// [`UpdatableCell::validated()`].
+    pub fn validator<V, E>(mut self, validator: V) -> Self
+    where
+        V: Fn(&T) -> Result<(), E> + Send + Sync + 'static,
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        self.validator = Some(Box::new(move |value: &T| {
+            validator(value).map_err(Into::into)
+        }));
+        self
+    }
+
+    /// Applies at most one replacement per `min_interval`, see
+    /// [`UpdatableCell::debounced()`].
+    pub fn debounce(mut self, min_interval: Duration) -> Self {
+        self.debounce = Some(Box::new(Debounce {
+            min_interval: nanos(min_interval),
+            epoch: Instant::now(),
+            next: AtomicU64::new(0),
+        }));
+        self
+    }
+
+    /// Sends every newly stored value to the returned receiver, see
+    /// [`UpdatableCell::with_channel()`].
+    #[cfg(feature = "crossbeam")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "crossbeam")))]
+    pub fn channel(mut self) -> (Self, crossbeam_channel::Receiver<Arc<T>>) {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        self.sender = Some(Box::new(sender));
+        (self, receiver)
+    }
+
+    /// Creates the cell.
+    pub fn build(self) -> UpdatableCell<T> {
+        let slot = Slot::new(ArcSwap::from_pointee(self.value));
+        UpdatableCell {
+            storage: if self.padded {
+                Storage::Padded(Box::new(CachePadded(slot)))
+            } else {
+                Storage::Inline(slot)
+            },
+            history: self.history,
+            refresh: self.refresh,
+            validator: self.validator,
+            debounce: self.debounce,
+            #[cfg(feature = "crossbeam")]
+            sender: self.sender,
+            notifier: OnceLock::new(),
+        }
+    }
+}
+
+impl<V> Slot<V> {
+    fn new(value: V) -> Self {
+        Slot {
+            value,
+            generation: AtomicU64::new(0),
+            retained: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl<T> LazySlot<T> {
+    fn slot(&self) -> &Slot<ArcSwap<T>> {
+        self.slot
+            .get_or_init(|| Slot::new(ArcSwap::from_pointee((self.init)())))
+    }
+}
+
+impl Notifier {
+    fn lock(&self) -> MutexGuard<'_, ()> {
+        self.lock.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn wake(&self) {
+        // Taking the lock orders this wakeup with a watcher that has just
+        // checked the value and is about to wait.
+        drop(self.lock());
+        self.changed.notify_all();
+        #[cfg(feature = "tokio")]
+        self.notify.notify_waiters();
+    }
+}
+
+impl<T> Refresh<T> {
+    fn now(&self) -> u64 {
+        nanos(self.epoch.elapsed())
+    }
+
+    fn is_stale(&self) -> bool {
+        let age = self
+            .now()
+            .saturating_sub(self.refreshed_at.load(Ordering::Acquire));
+        age >= self.ttl.load(Ordering::Relaxed)
+    }
+}
+
+fn nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+impl<T> History<T> {
+    fn lock(&self) -> MutexGuard<'_, VecDeque<Arc<T>>> {
+        self.snapshots.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn push(&self, snapshots: &mut VecDeque<Arc<T>>, snapshot: Arc<T>) {
+        if self.capacity == 0 {
+            return;
+        }
+        if snapshots.len() == self.capacity {
+            snapshots.pop_front();
+        }
+        snapshots.push_back(snapshot);
+    }
+}
+
+impl<T> From<Arc<T>> for UpdatableCell<T> {
+    fn from(value: Arc<T>) -> Self {
+        UpdatableCell::from_arc(value)
+    }
+}
+
+/// What [`AnyCow::try_replace_tracked()`](crate::AnyCow::try_replace_tracked)
+/// observed around a replacement.
+///
+/// Every store to a `Tracked` value bumps a generation counter. The writer
+/// reads the counter just before storing and gets back the generation its
+/// own store produced; any gap between the two means other writers stored
+/// in between. Readers don't show up here: `arc-swap` doesn't expose how
+/// many guards are outstanding.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct ReplaceStats {
+    /// The generation read just before storing.
+    pub observed_generation: u64,
+    /// The generation produced by this store.
+    pub generation: u64,
+}
+
+impl ReplaceStats {
+    /// Returns how many other stores landed between reading the generation
+    /// and this store.
+    ///
+    /// A replacement dropped by a debounced container didn't store, so it
+    /// reports `generation == observed_generation` and no concurrent writes.
+    pub fn concurrent_writes(&self) -> u64 {
+        (self.generation - self.observed_generation).saturating_sub(1)
+    }
+
+    /// Returns `true` if another writer stored concurrently.
+    pub fn was_contended(&self) -> bool {
+        self.concurrent_writes() > 0
+    }
+}
+
+/// Why [`UpdatableHandle::compare_and_swap()`] didn't store a value.
+#[derive(Debug)]
+pub enum CasError<T> {
+    /// The value was replaced in the meantime; holds the offered value.
+    Changed(Arc<T>),
+
+    /// The validator of a `Tracked` value refused the offered value.
+    Rejected(Rejected<Arc<T>>),
+}
+
+impl<T> CasError<T> {
+    /// Returns the offered value.
+    pub fn into_value(self) -> Arc<T> {
+        match self {
+            CasError::Changed(value) => value,
+            CasError::Rejected(rejected) => rejected.into_value(),
+        }
+    }
+}
+
+/// Typed access to the atomic operations of an `Updatable`, `Tracked` or
+/// `Lazy` value.
+///
+/// Created by [`AnyCow::as_updatable()`](crate::AnyCow::as_updatable),
+/// which performs the variant check once; every operation on the handle
+/// then works without a `Result` for the wrong variant. All writes go
+/// through the same path as [`AnyCow::try_replace()`](crate::AnyCow::try_replace),
+/// so history and watchers observe them, and the validator of a `Tracked`
+/// value can reject them.
+pub struct UpdatableHandle<'h, T> {
+    target: Target<'h, T>,
+}
+
+enum Target<'h, T> {
+    Plain(&'h ArcSwap<T>),
+    Cell(&'h UpdatableCell<T>),
+}
+
+impl<'h, T> UpdatableHandle<'h, T> {
+    pub(crate) fn new(cell: &'h UpdatableCell<T>) -> Self {
+        UpdatableHandle {
+            target: Target::Cell(cell),
+        }
+    }
+
+    pub(crate) fn plain(value: &'h ArcSwap<T>) -> Self {
+        UpdatableHandle {
+            target: Target::Plain(value),
+        }
+    }
+
+    /// Returns a guard to the current value.
+    pub fn load(&self) -> Guard<Arc<T>> {
+        match self.target {
+            Target::Plain(value) => value.load(),
+            Target::Cell(cell) => cell.load(),
+        }
+    }
+
+    /// Returns a full `Arc` to the current value.
+    pub fn load_full(&self) -> Arc<T> {
+        match self.target {
+            Target::Plain(value) => value.load_full(),
+            Target::Cell(cell) => cell.load_full(),
+        }
+    }
+
+    /// Atomically replaces the current value.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the value was stored
+    /// - `Err(rejected)` with the value if the validator refused it
+    pub fn store(&self, value: T) -> Result<(), Rejected<T>> {
+        self.swap(value).map(drop)
+    }
+
+    /// Atomically replaces the current value, returning the previous one.
+    ///
+    /// Fails like [`store()`](Self::store) if the validator refuses `value`.
+    pub fn swap(&self, value: T) -> Result<Arc<T>, Rejected<T>> {
+        self.swap_from(value)
+    }
+
+    pub(crate) fn store_arc(&self, value: Arc<T>) -> Result<(), Rejected<Arc<T>>> {
+        self.swap_from(value).map(drop)
+    }
+
+    fn swap_from<V>(&self, value: V) -> Result<Arc<T>, Rejected<V>>
+    where
+        V: Borrow<T> + Into<Arc<T>>,
+    {
+        match self.target {
+            Target::Plain(current) => {
+                let previous = current.swap(value.into());
+                published_plain();
+                Ok(previous)
+            }
+            Target::Cell(cell) => cell.swap_counted(value).map(|(previous, _)| previous),
+        }
+    }
+
+    /// Stores `new` only if the current value is still `current`.
+    ///
+    /// Values are compared by pointer, see [`Arc::ptr_eq`].
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(previous)` with the replaced value on success
+    /// - `Err(CasError::Changed(new))` if the value was replaced in the meantime
+    /// - `Err(CasError::Rejected(_))` if the validator refused `new`
+    pub fn compare_and_swap(&self, current: &Arc<T>, new: T) -> Result<Arc<T>, CasError<T>> {
+        self.compare_and_swap_arc(current, Arc::new(new))
+    }
+
+    pub(crate) fn compare_and_swap_arc(
+        &self,
+        current: &Arc<T>,
+        new: Arc<T>,
+    ) -> Result<Arc<T>, CasError<T>> {
+        match self.target {
+            Target::Plain(value) => {
+                let previous = value.compare_and_swap(current, new.clone());
+                if Arc::ptr_eq(&previous, current) {
+                    published_plain();
+                    Ok(Guard::into_inner(previous))
+                } else {
+                    Err(CasError::Changed(new))
+                }
+            }
+            Target::Cell(cell) => cell.compare_and_swap(current, new),
+        }
+    }
+
+    /// Read-copy-update: computes a new value from the current one and
+    /// stores it, retrying if another writer got there first.
+    ///
+    /// `f` may be called more than once. Returns the replaced value, or
+    /// the computed value if the validator refused it.
+    pub fn rcu<F>(&self, mut f: F) -> Result<Arc<T>, Rejected<Arc<T>>>
+    where
+        F: FnMut(&T) -> T,
+    {
+        let mut current = self.load_full();
+        loop {
+            match self.compare_and_swap(&current, f(&current)) {
+                Ok(previous) => return Ok(previous),
+                Err(CasError::Changed(_)) => current = self.load_full(),
+                Err(CasError::Rejected(rejected)) => return Err(rejected),
+            }
+        }
+    }
+
+    pub(crate) fn watch(&self) -> Watcher<'h, T> {
+        match self.target {
+            Target::Plain(value) => {
+                let watch = PlainWatch::start();
+                Watcher {
+                    source: Source::Plain {
+                        value,
+                        seen: value.load_full(),
+                        _watch: watch,
+                    },
+                }
+            }
+            Target::Cell(cell) => cell.watch(),
+        }
+    }
+
+    /// See [`AnyCow::changed()`](crate::AnyCow::changed).
+    #[cfg(feature = "tokio")]
+    pub(crate) fn changed(&self) -> impl std::future::Future<Output = Arc<T>> + 'h {
+        // Take the snapshot to compare against now, so stores between this
+        // call and the first poll count.
+        self.watch().changed()
+    }
+
+    pub(crate) fn cached(&self) -> Cached<'h, T> {
+        match self.target {
+            Target::Plain(value) => Cached {
+                cache: Cache::new(value),
+            },
+            Target::Cell(cell) => cell.cached(),
+        }
+    }
+
+    /// Returns the underlying cell of a `Tracked` value, or `None` for the
+    /// plain `ArcSwap` of an `Updatable` or `Lazy`.
+    pub fn cell(&self) -> Option<&'h UpdatableCell<T>> {
+        match self.target {
+            Target::Plain(_) => None,
+            Target::Cell(cell) => Some(cell),
+        }
+    }
+}
+
+impl<T> Clone for UpdatableHandle<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for UpdatableHandle<'_, T> {}
+
+impl<T> Clone for Target<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Target<'_, T> {}
+
+/// A reader that keeps the last loaded snapshot of an `Updatable` or `Lazy`
+/// value and only reloads it when the value has been replaced.
+///
+/// Created by [`AnyCow::cached_reader()`](crate::AnyCow::cached_reader).
+/// On the steady-state path [`get()`](Self::get) is a single cheap check
+/// instead of a full guard acquisition, which reduces atomic traffic in
+/// tight read loops. Because it updates its memoized snapshot, `get`
+/// takes `&mut self`; keep one `Cached` per thread.
+///
+/// The memoized snapshot is kept alive until the next `get` after a
+/// replacement, or until the reader is dropped.
+pub struct Cached<'c, T> {
+    cache: Cache<&'c ArcSwap<T>, Arc<T>>,
+}
+
+impl<T> Cached<'_, T> {
+    /// Returns the current value, reloading only if it was replaced.
+    pub fn get(&mut self) -> &T {
+        self.cache.load()
+    }
+
+    /// Returns the current value as an `Arc`, reloading only if it was replaced.
+    pub fn get_arc(&mut self) -> &Arc<T> {
+        self.cache.load()
+    }
+}
+
+/// A blocking iterator over changes to an `Updatable`, `Tracked` or `Lazy`
+/// value.
+///
+/// Created by [`AnyCow::watch()`](crate::AnyCow::watch). Each call to
+/// [`next()`](Iterator::next) blocks until the value has been replaced since
+/// the previous call (or since the watcher was created) and then yields the
+/// latest value. Replacements that happen while nobody is waiting are
+/// coalesced: a slow consumer sees the most recent value, not every
+/// intermediate one. The iterator never ends, so `for snapshot in watcher`
+/// runs until the thread breaks out of the loop.
+pub struct Watcher<'w, T> {
+    source: Source<'w, T>,
+}
+
+enum Source<'w, T> {
+    /// A `Tracked` value, whose generation tells replacements apart.
+    Cell {
+        cell: &'w UpdatableCell<T>,
+        seen: u64,
+    },
+    /// A plain `Updatable` or `Lazy` value, compared by pointer with the
+    /// last value seen. Holding that value keeps its address from being
+    /// reused by a later one.
+    Plain {
+        value: &'w ArcSwap<T>,
+        seen: Arc<T>,
+        _watch: PlainWatch,
+    },
+}
+
+impl<'w, T> Watcher<'w, T> {
+    /// Like [`next()`](Iterator::next), but gives up after `timeout`.
+    ///
+    /// Returns `None` if the value wasn't replaced in time.
+    pub fn next_timeout(&mut self, timeout: Duration) -> Option<Arc<T>> {
+        self.wait(Some(Instant::now() + timeout))
+    }
+
+    /// Returns `true` if the value was replaced since the last yielded
+    /// snapshot, without blocking.
+    pub fn has_changed(&self) -> bool {
+        match &self.source {
+            Source::Cell { cell, seen } => cell.generation().load(Ordering::SeqCst) != *seen,
+            Source::Plain { value, seen, .. } => !Arc::ptr_eq(&value.load(), seen),
+        }
+    }
+
+    /// Returns the latest value if it was replaced since the last one
+    /// seen, and marks it as seen.
+    fn poll(&mut self) -> Option<Arc<T>> {
+        match &mut self.source {
+            Source::Cell { cell, seen } => {
+                let current = cell.generation().load(Ordering::SeqCst);
+                if current == *seen {
+                    return None;
+                }
+                *seen = current;
+                // Bypass the refresh check: a reload stores and would try
+                // to take the notifier lock held here.
+                Some(cell.value().load_full())
+            }
+            Source::Plain { value, seen, .. } => {
+                let current = value.load_full();
+                if Arc::ptr_eq(&current, seen) {
+                    return None;
+                }
+                *seen = current.clone();
+                Some(current)
+            }
+        }
+    }
+
+    fn notifier(&self) -> &'w Notifier {
+        match self.source {
+            Source::Cell { cell, .. } => cell.notifier.get_or_init(Notifier::default),
+            Source::Plain { .. } => PLAIN_NOTIFIER.get_or_init(Notifier::default),
+        }
+    }
+
+    /// Resolves once [`poll()`](Self::poll) finds a new value.
+    #[cfg(feature = "tokio")]
+    pub(crate) async fn changed(mut self) -> Arc<T> {
+        let notifier = self.notifier();
+        loop {
+            // Register interest before checking the value, so a store in
+            // between still wakes this task.
+            let mut notified = std::pin::pin!(notifier.notify.notified());
+            notified.as_mut().enable();
+            if let Some(value) = self.poll() {
+                return value;
+            }
+            notified.await;
+        }
+    }
+
+    fn wait(&mut self, deadline: Option<Instant>) -> Option<Arc<T>> {
+        let notifier = self.notifier();
+        let mut guard = notifier.lock();
+        loop {
+            if let Some(value) = self.poll() {
+                return Some(value);
+            }
+            guard = match deadline {
+                None => notifier
+                    .changed
+                    .wait(guard)
+                    .unwrap_or_else(|e| e.into_inner()),
+                Some(deadline) => {
+                    let remaining = deadline.checked_duration_since(Instant::now())?;
+                    notifier
+                        .changed
+                        .wait_timeout(guard, remaining)
+                        .unwrap_or_else(|e| e.into_inner())
+                        .0
+                }
+            };
+        }
+    }
+}
+
+impl<T> Iterator for Watcher<'_, T> {
+    type Item = Arc<T>;
+
+    fn next(&mut self) -> Option<Arc<T>> {
+        self.wait(None)
+    }
+}
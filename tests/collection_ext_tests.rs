@@ -0,0 +1,85 @@
+use anycow::{AnyCow, UpdatableCollectionExt};
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::thread;
+
+#[test]
+fn test_sequence_push_and_remove() {
+    let queue = AnyCow::updatable(VecDeque::from([1, 2]));
+    queue.push(3).unwrap();
+    queue.remove(&0).unwrap();
+    assert_eq!(*queue.borrow(), VecDeque::from([2, 3]));
+
+    let list = AnyCow::lazy(Vec::new);
+    list.insert("a").unwrap();
+    assert_eq!(*list.borrow(), vec!["a"]);
+}
+
+#[test]
+fn test_sequence_remove_out_of_bounds_is_noop() {
+    let queue = AnyCow::updatable(VecDeque::from([1]));
+    queue.remove(&5).unwrap();
+    assert_eq!(*queue.borrow(), VecDeque::from([1]));
+
+    let list = AnyCow::updatable(vec![1]);
+    list.remove(&1).unwrap();
+    assert_eq!(*list.borrow(), vec![1]);
+}
+
+#[test]
+fn test_sets_and_maps() {
+    let set = AnyCow::updatable(HashSet::new());
+    set.insert(1).unwrap();
+    set.insert(1).unwrap();
+    set.remove(&2).unwrap();
+    assert_eq!(set.borrow().len(), 1);
+
+    let map = AnyCow::updatable(BTreeMap::new());
+    map.insert(("b", 2)).unwrap();
+    map.insert(("a", 1)).unwrap();
+    map.remove(&"b").unwrap();
+    assert_eq!(*map.borrow(), BTreeMap::from([("a", 1)]));
+}
+
+#[test]
+fn test_fixed_variants_are_rejected() {
+    let owned = AnyCow::owned(vec![1]);
+    assert!(owned.push(2).is_err());
+    assert!(AnyCow::shared(Arc::new(vec![1])).remove(&0).is_err());
+}
+
+#[test]
+fn test_concurrent_pushes_are_not_lost() {
+    let list = AnyCow::updatable(Vec::new());
+    thread::scope(|s| {
+        for t in 0..4 {
+            let list = &list;
+            s.spawn(move || {
+                for i in 0..50 {
+                    list.push(t * 100 + i).unwrap();
+                }
+            });
+        }
+    });
+    assert_eq!(list.borrow().len(), 200);
+}
+
+#[test]
+fn test_insert_many_publishes_one_state() {
+    let map = AnyCow::updatable_tracked(BTreeMap::from([(0, 0)]));
+    let mut watcher = map.watch().unwrap();
+
+    map.insert_many((1..=3).map(|i| (i, i * 10))).unwrap();
+    assert_eq!(watcher.next().unwrap().len(), 4);
+    assert!(!watcher.has_changed());
+
+    map.insert_many([]).unwrap();
+    assert!(!watcher.has_changed());
+
+    let set = AnyCow::lazy(HashSet::new);
+    set.insert_many(["a", "b", "a"]).unwrap();
+    assert_eq!(set.borrow().len(), 2);
+
+    assert!(AnyCow::owned(vec![1]).insert_many([2]).is_err());
+    assert!(AnyCow::owned(vec![1]).insert_many([]).is_err());
+}
@@ -0,0 +1,61 @@
+#![allow(
+    clippy::declare_interior_mutable_const,
+    clippy::borrow_interior_mutable_const
+)]
+//! Compile-time checks for every constructor advertised as `const`.
+use anycow::AnyCow;
+use std::sync::Arc;
+
+static GREETING: &str = "hello";
+
+const BORROWED: AnyCow<&str> = AnyCow::borrowed(&GREETING);
+static BORROWED_STATIC: AnyCow<&str> = AnyCow::borrowed(&GREETING);
+
+const LAZY: AnyCow<Vec<i32>> = AnyCow::lazy(|| vec![1, 2, 3]);
+static LAZY_STATIC: AnyCow<Vec<i32>> = AnyCow::lazy(|| vec![1, 2, 3]);
+
+const EMPTY_NAMES: AnyCow<Vec<String>> = AnyCow::empty();
+static EMPTY_LABEL: AnyCow<String> = AnyCow::empty();
+
+// `Arc::new` isn't const, but `shared` can still be used from const code.
+const fn shared_in_const<T: Clone>(value: Arc<T>) -> AnyCow<'static, T> {
+    AnyCow::shared(value)
+}
+
+// The `is_*` predicates are usable from const code as well.
+const fn is_direct<T: Clone>(cow: &AnyCow<'_, T>) -> bool {
+    let direct = cow.is_borrowed() || cow.is_owned() || cow.is_shared();
+    let guarded = cow.is_updatable() || cow.is_lazy();
+    direct && !guarded && !cow.is_guarded_read()
+}
+
+#[test]
+fn test_const_borrowed() {
+    assert!(BORROWED.is_borrowed());
+    assert_eq!(*BORROWED_STATIC.borrow(), "hello");
+    assert!(is_direct(&BORROWED));
+}
+
+#[test]
+fn test_const_lazy() {
+    assert!(LAZY.is_lazy());
+    assert!(!is_direct(&LAZY));
+    assert_eq!(*LAZY_STATIC.borrow(), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_const_shared() {
+    let cow = shared_in_const(Arc::new(5));
+    assert!(cow.is_shared());
+    assert_eq!(*cow.borrow(), 5);
+}
+
+#[test]
+fn test_const_empty() {
+    assert!(EMPTY_NAMES.is_borrowed());
+    assert!(EMPTY_NAMES.borrow().is_empty());
+    assert_eq!(*EMPTY_LABEL.borrow(), "");
+
+    let none: AnyCow<Option<u8>> = AnyCow::empty();
+    assert_eq!(*none.borrow(), None);
+}
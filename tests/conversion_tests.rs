@@ -0,0 +1,711 @@
+use anycow::{AnyCow, NotOwned};
+use std::borrow::Cow;
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Counts how often it (or any of its clones) is cloned.
+#[derive(Default)]
+struct Counted {
+    clones: Arc<AtomicUsize>,
+}
+
+impl Clone for Counted {
+    fn clone(&self) -> Self {
+        self.clones.fetch_add(1, Ordering::SeqCst);
+        Counted {
+            clones: self.clones.clone(),
+        }
+    }
+}
+
+#[test]
+fn test_into_shared_owned() {
+    let owned = AnyCow::owned(String::from("owned"));
+    let shared = owned.into_shared();
+
+    assert!(shared.is_shared());
+    assert_eq!(*shared.borrow(), "owned");
+}
+
+#[test]
+fn test_into_shared_borrowed() {
+    let data = String::from("borrowed");
+    let shared = AnyCow::borrowed(&data).into_shared();
+
+    // Borrowed data is cloned so the result no longer depends on `data`
+    drop(data);
+    assert!(shared.is_shared());
+    assert_eq!(*shared.borrow(), "borrowed");
+}
+
+#[test]
+fn test_into_shared_reuses_arc() {
+    let arc = Arc::new(vec![1, 2, 3]);
+    let shared = AnyCow::shared(arc.clone()).into_shared();
+    assert!(Arc::ptr_eq(&arc, &shared.to_arc()));
+
+    let updatable = AnyCow::updatable(vec![4, 5, 6]);
+    let snapshot = updatable.to_arc();
+    let shared = updatable.into_shared();
+    assert!(Arc::ptr_eq(&snapshot, &shared.to_arc()));
+}
+
+#[test]
+fn test_into_shared_lazy() {
+    let lazy = AnyCow::lazy(|| vec![7, 8, 9]);
+    let shared = lazy.into_shared();
+
+    assert!(shared.is_shared());
+    assert_eq!(*shared.borrow(), vec![7, 8, 9]);
+
+    let lazy = AnyCow::lazy(|| vec![1]);
+    let snapshot = lazy.to_arc();
+    let shared = lazy.into_shared();
+    assert!(Arc::ptr_eq(&snapshot, &shared.to_arc()));
+}
+
+#[test]
+fn test_into_shared_borrowed_is_sendable() {
+    let data = String::from("promoted");
+    let shared = AnyCow::borrowed(&data).into_shared();
+
+    let handle = std::thread::spawn(move || shared.borrow().clone());
+    assert_eq!(handle.join().unwrap(), "promoted");
+}
+
+#[test]
+fn test_from_box_reuses_allocation() {
+    let boxed = Box::new([0u8; 64]);
+    let ptr: *const [u8; 64] = &*boxed;
+
+    let cow: AnyCow<[u8; 64]> = boxed.into();
+    assert!(cow.is_owned());
+    assert!(std::ptr::eq(ptr, &*cow.borrow()));
+}
+
+#[test]
+fn test_project_variants() {
+    let shared = AnyCow::shared(Arc::new((1, String::from("two"))));
+    let second = shared.project(|pair| &pair.1).unwrap();
+    assert!(second.is_borrowed());
+    assert_eq!(*second.borrow(), "two");
+
+    let updatable = AnyCow::updatable((1, String::from("two")));
+    assert!(updatable.project(|pair| &pair.0).is_none());
+}
+
+#[test]
+fn test_raw_shared_roundtrip() {
+    let arc = Arc::new(vec![1, 2, 3]);
+    let ptr = AnyCow::shared(arc.clone()).into_raw_shared().unwrap();
+    assert!(std::ptr::eq(ptr, Arc::as_ptr(&arc)));
+
+    // SAFETY: `ptr` came from `into_raw_shared` and is reclaimed once
+    let cow = unsafe { AnyCow::from_raw_shared(ptr) };
+    assert_eq!(Arc::strong_count(&arc), 2);
+    drop(cow);
+    assert_eq!(Arc::strong_count(&arc), 1);
+
+    let updatable = AnyCow::updatable(String::from("snapshot"));
+    let ptr = updatable.into_raw_shared().unwrap();
+    // SAFETY: as above
+    let cow = unsafe { AnyCow::from_raw_shared(ptr) };
+    assert!(cow.is_shared());
+    assert_eq!(*cow.borrow(), "snapshot");
+
+    let owned = AnyCow::owned(1);
+    assert!(owned.into_raw_shared().unwrap_err().is_owned());
+}
+
+#[test]
+fn test_to_mut_from_every_variant() {
+    let data = String::from("borrowed");
+    let mut cows = vec![
+        AnyCow::borrowed(&data),
+        AnyCow::owned(String::from("owned")),
+        AnyCow::shared(Arc::new(String::from("shared"))),
+        AnyCow::updatable(String::from("updatable")),
+        AnyCow::lazy(|| String::from("lazy")),
+    ];
+
+    for cow in &mut cows {
+        let before = cow.borrow().clone();
+        cow.to_mut().push('!');
+        assert!(cow.is_owned());
+        assert_eq!(*cow.borrow(), format!("{before}!"));
+    }
+    assert_eq!(data, "borrowed");
+}
+
+#[test]
+fn test_to_mut_owned_keeps_allocation() {
+    let mut cow = AnyCow::owned([0u8; 32]);
+    let before: *const [u8; 32] = &*cow.borrow();
+    cow.to_mut()[0] = 1;
+    assert!(std::ptr::eq(before, &*cow.borrow()));
+}
+
+#[test]
+fn test_to_mut_unique_shared_moves_value() {
+    let arc = Arc::new(Counted::default());
+    let clones = arc.clones.clone();
+    let mut cow = AnyCow::shared(arc);
+    cow.to_mut();
+    assert!(cow.is_owned());
+    assert_eq!(clones.load(Ordering::SeqCst), 0);
+
+    let arc = Arc::new(Counted::default());
+    let clones = arc.clones.clone();
+    let mut cow = AnyCow::shared(arc.clone());
+    cow.to_mut();
+    assert!(cow.is_owned());
+    assert_eq!(clones.load(Ordering::SeqCst), 1);
+
+    // A `Weak` could be upgraded at any time, so the value is cloned.
+    let arc = Arc::new(Counted::default());
+    let clones = arc.clones.clone();
+    let weak = Arc::downgrade(&arc);
+    let mut cow = AnyCow::shared(arc);
+    cow.to_mut();
+    assert!(cow.is_owned());
+    assert_eq!(clones.load(Ordering::SeqCst), 1);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_into_owned_unique_updatable_moves_value() {
+    let cow = AnyCow::updatable(Counted::default());
+    let clones = cow.borrow().clones.clone();
+    cow.try_replace(Counted {
+        clones: clones.clone(),
+    })
+    .unwrap();
+    let _value = cow.into_owned();
+    assert_eq!(clones.load(Ordering::SeqCst), 0);
+
+    let cow = AnyCow::updatable(Counted::default());
+    let clones = cow.borrow().clones.clone();
+    let snapshot = cow.to_arc();
+    let _value = cow.into_owned();
+    assert_eq!(clones.load(Ordering::SeqCst), 1);
+    drop(snapshot);
+
+    let lazy = AnyCow::lazy(Counted::default);
+    let clones = lazy.borrow().clones.clone();
+    let _value = lazy.into_owned();
+    assert_eq!(clones.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn test_into_inner_arc() {
+    let cow = AnyCow::updatable(1);
+    cow.try_replace(2).unwrap();
+    assert_eq!(*cow.into_inner_arc().unwrap(), 2);
+
+    let lazy = AnyCow::lazy(|| 3);
+    let lazy = lazy.into_inner_arc().unwrap_err();
+    assert!(lazy.is_lazy());
+    let _ = lazy.borrow();
+    assert_eq!(*lazy.into_inner_arc().unwrap(), 3);
+
+    let value = 4;
+    assert!(AnyCow::borrowed(&value).into_inner_arc().is_err());
+}
+
+#[test]
+fn test_from_array_and_slice() {
+    let cow: AnyCow<Vec<String>> = [String::from("a"), String::from("b")].into();
+    assert!(cow.is_owned());
+    assert_eq!(cow.borrow().len(), 2);
+
+    let empty: AnyCow<Vec<u8>> = [].into();
+    assert!(empty.borrow().is_empty());
+
+    let source = vec![1, 2, 3];
+    let cow: AnyCow<Vec<i32>> = AnyCow::from(&source[..]);
+    assert!(cow.is_owned());
+    assert_eq!(*cow.borrow(), source);
+
+    // A `&Vec` still borrows
+    let cow: AnyCow<Vec<i32>> = AnyCow::from(&source);
+    assert!(cow.is_borrowed());
+}
+
+#[test]
+fn test_heap_size() {
+    use std::mem::size_of;
+
+    let text = String::with_capacity(64);
+    assert_eq!(AnyCow::borrowed(&text).heap_size(), 0);
+    let capacity = text.capacity();
+    assert_eq!(
+        AnyCow::owned(text).heap_size(),
+        size_of::<String>() + capacity
+    );
+
+    let shared = AnyCow::shared(Arc::new(7u64));
+    assert_eq!(
+        shared.heap_size(),
+        2 * size_of::<usize>() + size_of::<u64>()
+    );
+
+    let lazy = AnyCow::lazy(|| vec![1u32; 4]);
+    assert_eq!(lazy.heap_size(), 0);
+    let _ = lazy.borrow();
+    assert!(lazy.heap_size() >= 4 * size_of::<u32>());
+
+    let nested = AnyCow::owned(vec![String::with_capacity(10)]);
+    assert_eq!(
+        nested.heap_size(),
+        size_of::<Vec<String>>() + size_of::<String>() + 10
+    );
+}
+
+#[test]
+fn test_heap_size_counts_tracked_cell() {
+    use std::mem::size_of;
+
+    let plain = AnyCow::updatable(7u64);
+    let tracked = AnyCow::updatable_with_history(7u64, 4);
+    assert!(
+        tracked.heap_size()
+            >= plain.heap_size() + size_of::<anycow::UpdatableCell<u64>>() + 4 * size_of::<usize>()
+    );
+}
+
+#[test]
+fn test_or_fallback_chain() {
+    let fallback_calls = std::cell::Cell::new(0);
+    let fallback = || {
+        fallback_calls.set(fallback_calls.get() + 1);
+        AnyCow::owned(String::from("default"))
+    };
+
+    let value = AnyCow::owned(String::new()).or_else(fallback);
+    assert_eq!(*value.borrow(), "default");
+
+    let value = AnyCow::owned(String::from("override")).or_else(fallback);
+    assert_eq!(*value.borrow(), "override");
+    assert_eq!(fallback_calls.get(), 1);
+
+    let chained = AnyCow::owned(None)
+        .or(AnyCow::owned(None))
+        .or(AnyCow::updatable(Some(3)));
+    assert!(chained.is_updatable());
+    assert_eq!(*chained.borrow(), Some(3));
+}
+
+#[test]
+fn test_clone_from_reuses_owned_allocation() {
+    let source = AnyCow::owned(String::from("hello"));
+    let mut target = AnyCow::owned(String::with_capacity(32));
+    let box_ptr = target.as_direct().unwrap() as *const String;
+
+    target.clone_from(&source);
+    assert_eq!(*target.borrow(), "hello");
+    assert_eq!(target.borrow().capacity(), 32);
+    assert_eq!(target.as_direct().unwrap() as *const String, box_ptr);
+
+    // Other combinations fall back to a regular clone
+    let shared = AnyCow::shared(Arc::new(String::from("shared")));
+    target.clone_from(&shared);
+    assert!(target.is_shared());
+    assert_eq!(*target.borrow(), "shared");
+}
+
+#[test]
+fn test_add_and_add_assign() {
+    let shared = AnyCow::shared(Arc::new(String::from("a")));
+    let joined = shared.clone() + "b" + "c";
+    assert!(joined.is_owned());
+    assert_eq!(*joined.borrow(), "abc");
+    assert_eq!(*shared.borrow(), "a");
+
+    let mut text = shared;
+    text += "z";
+    assert!(text.is_owned());
+    assert_eq!(*text.borrow(), "az");
+
+    let mut lazy = AnyCow::lazy(|| vec![1]);
+    lazy += &[2][..];
+    assert!(lazy.is_lazy());
+    assert_eq!(*lazy.borrow(), vec![1, 2]);
+}
+
+#[test]
+fn test_freeze_and_thaw() {
+    let data = Arc::new(vec![1, 2]);
+    let mut cow = AnyCow::shared(data.clone());
+    cow.thaw().unwrap();
+    assert!(cow.is_updatable());
+    assert!(Arc::ptr_eq(&cow.to_arc(), &data));
+
+    cow.freeze().unwrap();
+    cow.freeze().unwrap();
+    assert!(cow.is_shared());
+    assert!(Arc::ptr_eq(&cow.to_arc(), &data));
+
+    let mut lazy = AnyCow::lazy(|| 5);
+    lazy.freeze().unwrap();
+    assert!(lazy.is_shared());
+    assert_eq!(*lazy.borrow(), 5);
+
+    let mut owned = AnyCow::owned(1);
+    assert!(owned.freeze().is_err());
+    assert!(owned.thaw().is_err());
+    assert!(owned.is_owned());
+}
+
+#[test]
+fn test_by_value_snapshots_guarded_variants() {
+    use anycow::ByValue;
+    use std::collections::HashMap;
+
+    let live = AnyCow::updatable(1);
+    let key = ByValue::new(live.clone());
+    let mut map = HashMap::new();
+    map.insert(key.clone(), "one");
+
+    live.try_replace(2).unwrap();
+    assert_eq!(map.get(&ByValue::new(AnyCow::owned(1))), Some(&"one"));
+    assert!(key.clone().into_inner().is_shared());
+    assert_eq!(*key, 1);
+
+    let lazy = ByValue::from(AnyCow::lazy(|| 3));
+    assert_eq!(*lazy.get(), 3);
+
+    let value = 4;
+    assert!(ByValue::new(AnyCow::borrowed(&value))
+        .into_inner()
+        .is_borrowed());
+}
+
+#[test]
+fn test_as_direct() {
+    let value = 1;
+    assert_eq!(AnyCow::borrowed(&value).as_direct(), Some(&1));
+    assert_eq!(AnyCow::owned(2).as_direct(), Some(&2));
+
+    let data = Arc::new(3);
+    let shared = AnyCow::shared(data.clone());
+    assert!(std::ptr::eq(shared.as_direct().unwrap(), &*data));
+
+    assert!(AnyCow::lazy(|| 4).as_direct().is_none());
+}
+
+#[test]
+fn test_unzip() {
+    let pair = (String::from("left"), vec![1, 2]);
+    let (left, right) = AnyCow::borrowed(&pair).unzip();
+    assert!(left.is_owned() && right.is_owned());
+    assert_eq!(*left.borrow(), "left");
+    assert_eq!(*right.borrow(), vec![1, 2]);
+
+    let shared = Arc::new((1, 'x'));
+    let (number, letter) = AnyCow::shared(shared.clone()).unzip();
+    assert_eq!((*number.borrow(), *letter.borrow()), (1, 'x'));
+    assert_eq!(Arc::strong_count(&shared), 1);
+}
+
+#[test]
+fn test_set_owned_replaces_any_variant() {
+    let mut cow = AnyCow::updatable(1);
+    cow.set_owned(Box::new(2));
+    assert!(cow.is_owned());
+    assert_eq!(*cow.borrow(), 2);
+
+    let mut cow = AnyCow::owned(String::from("old"));
+    cow.set_owned(Box::new(String::from("new")));
+    assert_eq!(*cow.borrow(), "new");
+}
+
+#[test]
+fn test_owned_mut_never_clones() {
+    let mut owned = AnyCow::owned(Counted::default());
+    assert!(owned.owned_mut().is_ok());
+
+    let value = Counted::default();
+    let clones = value.clones.clone();
+    let mut borrowed = AnyCow::borrowed(&value);
+    assert_eq!(borrowed.owned_mut().err(), Some(NotOwned));
+    let mut shared = AnyCow::shared(Arc::new(value.clone()));
+    assert!(shared.owned_mut().is_err());
+    let mut updatable = AnyCow::updatable(value.clone());
+    assert!(updatable.owned_mut().is_err());
+    let mut lazy = AnyCow::<Counted>::lazy(Counted::default);
+    assert!(lazy.owned_mut().is_err());
+
+    assert!(borrowed.is_borrowed() && shared.is_shared() && updatable.is_updatable());
+    assert!(!lazy.is_initialized());
+    // Only the two explicit clones above
+    assert_eq!(clones.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_zip_round_trips_with_unzip() {
+    let name = String::from("cache");
+    let pair = AnyCow::borrowed(&name).zip(AnyCow::shared(Arc::new(7)));
+    assert!(pair.is_owned());
+    assert_eq!(*pair.borrow(), (String::from("cache"), 7));
+
+    let (left, right) = pair.unzip();
+    assert_eq!(*left.borrow(), "cache");
+    assert_eq!(*right.borrow(), 7);
+}
+
+#[test]
+fn test_downcast_ref() {
+    use std::any::Any;
+
+    let value: Arc<dyn Any + Send + Sync> = Arc::new(vec![1u8, 2]);
+    let cow = AnyCow::shared(Arc::new(value));
+    assert_eq!(cow.downcast_ref::<Vec<u8>>().as_deref(), Some(&vec![1, 2]));
+    assert!(cow.downcast_ref::<String>().is_none());
+
+    let lazy: AnyCow<Arc<dyn Any + Send + Sync>> = AnyCow::lazy(|| Arc::new(3.5f64));
+    assert_eq!(lazy.downcast_ref::<f64>().as_deref(), Some(&3.5));
+}
+
+#[test]
+fn test_string_helpers_avoid_needless_copies() {
+    let data = Arc::new(String::from("already lower"));
+    let lower = AnyCow::shared(data.clone()).to_lowercase();
+    assert!(lower.is_shared());
+    assert!(Arc::ptr_eq(&lower.to_arc(), &data));
+
+    let updatable = AnyCow::updatable(String::from("  MiXed  "));
+    assert_eq!(&*updatable.trim(), "MiXed");
+    assert_eq!(*updatable.to_lowercase().borrow(), "  mixed  ");
+
+    let mut owned = AnyCow::owned(String::from("id-42"));
+    owned.make_ascii_uppercase();
+    assert_eq!(*owned.borrow(), "ID-42");
+}
+
+#[test]
+fn test_into_owned_or_arc() {
+    assert_eq!(AnyCow::owned(1).into_owned_or_arc(), Ok(1));
+    assert_eq!(AnyCow::lazy(|| 2).into_owned_or_arc(), Ok(2));
+    assert_eq!(AnyCow::updatable(3).into_owned_or_arc(), Ok(3));
+
+    let cow = AnyCow::updatable(String::from("held"));
+    let snapshot = cow.to_arc();
+    let arc = cow.into_owned_or_arc().unwrap_err();
+    assert!(Arc::ptr_eq(&arc, &snapshot));
+}
+
+#[test]
+fn test_partial_eq_across_payload_types() {
+    let owned = AnyCow::owned(String::from("config"));
+    let updatable = AnyCow::updatable("config");
+    assert!(owned == updatable);
+    assert!(owned != AnyCow::owned("other"));
+    assert_eq!(AnyCow::owned(1), AnyCow::lazy(|| 1));
+}
+
+#[test]
+fn test_snapshot_outlives_container() {
+    fn assert_send_sync<S: Send + Sync>(_: &S) {}
+
+    let data = Arc::new(vec![1, 2, 3]);
+    let snapshot = {
+        let cow = AnyCow::shared(data.clone());
+        cow.snapshot()
+    };
+    assert_send_sync(&snapshot);
+    assert!(Arc::ptr_eq(&snapshot.clone().into_arc(), &data));
+
+    let value = String::from("borrowed");
+    let snapshot = AnyCow::borrowed(&value).snapshot();
+    drop(value);
+    assert_eq!(*snapshot, "borrowed");
+}
+
+#[test]
+fn test_path_payloads_as_ref() {
+    fn file_name(path: impl AsRef<Path>) -> Option<String> {
+        path.as_ref()
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+    }
+
+    let path = PathBuf::from("/etc/hosts");
+    let borrowed = AnyCow::borrowed(&path);
+    assert_eq!(file_name(borrowed.borrow()).as_deref(), Some("hosts"));
+    let updatable = AnyCow::updatable(path.clone());
+    assert_eq!(file_name(updatable.borrow()).as_deref(), Some("hosts"));
+
+    let name = AnyCow::owned(OsString::from("hosts"));
+    let name = name.borrow();
+    let os_str: &OsStr = name.as_ref();
+    assert_eq!(os_str, "hosts");
+}
+
+#[test]
+fn test_into_std_cow() {
+    let data = String::from("borrowed");
+    assert!(matches!(
+        Cow::from(AnyCow::borrowed(&data)),
+        Cow::Borrowed(_)
+    ));
+
+    assert!(matches!(Cow::from(AnyCow::updatable(42)), Cow::Owned(42)));
+}
+
+#[test]
+fn test_into_static_keeps_variants() {
+    let data = String::from("borrowed");
+    let owned = AnyCow::borrowed(&data).into_static();
+    drop(data);
+    assert!(owned.is_owned());
+    assert_eq!(*owned.borrow(), "borrowed");
+
+    let arc = Arc::new(String::from("shared"));
+    let shared = AnyCow::shared(arc.clone()).into_static();
+    assert!(Arc::ptr_eq(&arc, &shared.to_arc()));
+
+    let lazy = AnyCow::lazy(|| String::from("lazy")).into_static();
+    assert!(lazy.is_lazy());
+    assert!(!lazy.is_initialized());
+}
+
+#[test]
+fn test_intern_deduplicates_equal_values() {
+    let mut pool = std::collections::HashSet::new();
+    let data = String::from("eu");
+    let interned: Vec<_> = vec![
+        AnyCow::borrowed(&data),
+        AnyCow::owned(String::from("eu")),
+        AnyCow::lazy(|| String::from("eu")),
+        AnyCow::owned(String::from("us")),
+    ]
+    .into_iter()
+    .map(|cow| cow.intern(&mut pool))
+    .collect();
+
+    assert!(interned.iter().all(AnyCow::is_shared));
+    assert!(Arc::ptr_eq(&interned[0].to_arc(), &interned[2].to_arc()));
+    assert!(!Arc::ptr_eq(&interned[0].to_arc(), &interned[3].to_arc()));
+    assert_eq!(pool.len(), 2);
+
+    // The first container's own Arc is what goes into the pool
+    let arc = Arc::new(String::from("ap"));
+    AnyCow::shared(arc.clone()).intern(&mut pool);
+    assert!(pool.iter().any(|pooled| Arc::ptr_eq(pooled, &arc)));
+}
+
+#[test]
+fn test_into_vec_reuses_unique_allocation() {
+    let items = vec![1, 2, 3];
+    let address = items.as_ptr();
+    let cow = AnyCow::shared(Arc::new(items));
+    let unwrapped = cow.into_vec();
+    assert_eq!(unwrapped.as_ptr(), address);
+
+    let arc = Arc::new(vec![4, 5]);
+    let cow = AnyCow::shared(arc.clone());
+    let copy = cow.into_vec();
+    assert_ne!(copy.as_ptr(), arc.as_ptr());
+
+    let items = vec![6, 7];
+    let address = items.as_ptr();
+    let boxed = AnyCow::owned(items).into_boxed_slice();
+    assert_eq!(boxed.as_ptr(), address);
+}
+
+#[test]
+fn test_to_mut_tracked_reports_source() {
+    use anycow::MaterializedFrom;
+
+    let data = String::from("borrowed");
+    let cases = vec![
+        (
+            AnyCow::borrowed(&data),
+            MaterializedFrom::ClonedFromBorrowed,
+        ),
+        (AnyCow::owned(data.clone()), MaterializedFrom::AlreadyOwned),
+        (
+            AnyCow::shared(Arc::new(data.clone())),
+            MaterializedFrom::MovedFromShared,
+        ),
+        (
+            AnyCow::updatable(data.clone()),
+            MaterializedFrom::ClonedFromUpdatable,
+        ),
+        (
+            AnyCow::lazy(|| String::from("lazy")),
+            MaterializedFrom::ClonedFromLazy,
+        ),
+    ];
+    for (mut cow, expected) in cases {
+        let (value, from) = cow.to_mut_tracked();
+        value.push('!');
+        assert_eq!(from, expected);
+        assert!(cow.is_owned());
+    }
+
+    let arc = Arc::new(1);
+    let mut shared = AnyCow::shared(arc.clone());
+    assert_eq!(
+        shared.to_mut_tracked().1,
+        MaterializedFrom::ClonedFromShared
+    );
+}
+
+#[test]
+fn test_any_cow_ref_cloned_and_into_arc() {
+    use anycow::AnyCowRef;
+
+    let arc = Arc::new(Some(String::from("shared")));
+    let cow = AnyCow::shared(arc.clone());
+    let copy: Option<String> = AnyCowRef::cloned(&cow.borrow());
+    assert_eq!(copy.as_deref(), Some("shared"));
+
+    let into = AnyCowRef::into_arc(cow.borrow());
+    assert!(!Arc::ptr_eq(&into, &arc));
+    assert!(Arc::ptr_eq(&cow.to_arc(), &arc));
+
+    let updatable = AnyCow::updatable(vec![1]);
+    let guard = updatable.borrow();
+    updatable.try_replace(vec![2]).unwrap();
+    assert_eq!(*AnyCowRef::into_arc(guard), vec![1]);
+}
+
+#[test]
+fn test_shared_cyclic_points_to_itself() {
+    #[derive(Clone)]
+    struct Node {
+        this: std::sync::Weak<Node>,
+        upgraded_during_init: bool,
+    }
+
+    let node = AnyCow::shared_cyclic(|this| Node {
+        this: this.clone(),
+        upgraded_during_init: this.upgrade().is_some(),
+    });
+    assert!(node.is_shared());
+    assert!(!node.borrow().upgraded_during_init);
+    assert!(Arc::ptr_eq(
+        &node.borrow().this.upgrade().unwrap(),
+        &node.to_arc()
+    ));
+
+    let weak = node.borrow().this.clone();
+    drop(node);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_enum_size_bound() {
+    // Documented in the `AnyCow` memory layout section
+    assert_eq!(
+        std::mem::size_of::<AnyCow<u8>>(),
+        4 * std::mem::size_of::<usize>()
+    );
+    assert_eq!(
+        std::mem::size_of::<AnyCow<String>>(),
+        4 * std::mem::size_of::<usize>()
+    );
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "crossbeam")]
+
+use anycow::AnyCow;
+use std::sync::Arc;
+use std::thread;
+
+#[test]
+fn test_channel_receives_every_replacement() {
+    let (cow, updates) = AnyCow::updatable_channel(vec![0]);
+    cow.try_replace(vec![1]).unwrap();
+    cow.try_replace_arc(Arc::new(vec![2])).unwrap();
+    cow.retain(|&n| n != 2).unwrap();
+
+    let received: Vec<Vec<i32>> = updates.try_iter().map(|v| (*v).clone()).collect();
+    assert_eq!(received, vec![vec![1], vec![2], vec![]]);
+}
+
+#[test]
+fn test_channel_shares_stored_arc() {
+    let (cow, updates) = AnyCow::updatable_channel(String::new());
+    cow.try_replace(String::from("v1")).unwrap();
+    assert!(Arc::ptr_eq(&updates.recv().unwrap(), &cow.to_arc()));
+}
+
+#[test]
+fn test_dropped_receiver_is_harmless() {
+    let (cow, updates) = AnyCow::updatable_channel(1);
+    drop(updates);
+    cow.try_replace(2).unwrap();
+    assert_eq!(*cow.borrow(), 2);
+}
+
+#[test]
+fn test_concurrent_writers_send_everything() {
+    let (cow, updates) = AnyCow::updatable_channel(0);
+    thread::scope(|s| {
+        for t in 0..4 {
+            let cow = &cow;
+            s.spawn(move || {
+                for i in 0..25 {
+                    cow.try_replace(t * 100 + i).unwrap();
+                }
+            });
+        }
+    });
+    assert_eq!(updates.try_iter().count(), 100);
+}
@@ -2,10 +2,10 @@
     clippy::declare_interior_mutable_const,
     clippy::borrow_interior_mutable_const
 )]
-use anycow::AnyCow;
+use anycow::{AnyCow, WasInitialized};
 use std::ops::Deref;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 static INIT_COUNTER: AtomicUsize = AtomicUsize::new(0);
 
@@ -202,3 +202,177 @@ fn test_to_shared_vs_to_arc() {
     // We can't check if it's shared because to_arc returns Arc<T>, not AnyCow
     assert_eq!(*arc, "test");
 }
+
+#[test]
+fn test_borrow_or_init_with_runs_once() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let lazy: Arc<AnyCow<usize>> = Arc::new(AnyCow::lazy(|| unreachable!()));
+
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let lazy = lazy.clone();
+            let calls = calls.clone();
+            std::thread::spawn(move || {
+                *lazy.borrow_or_init_with(|| {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    99
+                })
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), 99);
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(*lazy.borrow(), 99);
+}
+
+#[test]
+fn test_debug_value_does_not_initialize() {
+    let lazy = AnyCow::lazy(|| vec![1, 2]);
+    assert_eq!(format!("{:?}", lazy.debug_value()), "<uninitialized>");
+    assert_eq!(format!("{:?}", lazy), "Lazy(\"<uninitialized>\")");
+
+    lazy.borrow();
+    assert_eq!(format!("{:?}", lazy.debug_value()), "[1, 2]");
+    assert_eq!(format!("{:#?}", lazy.debug_value()), "[\n    1,\n    2,\n]");
+}
+
+#[test]
+fn test_lazy_boxed_captured_prefix() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let prefix = String::from("cfg");
+    let counter = calls.clone();
+    let lazy = Arc::new(AnyCow::lazy_boxed(move || {
+        counter.fetch_add(1, Ordering::SeqCst);
+        format!("{prefix}:loaded")
+    }));
+    assert!(!lazy.is_initialized());
+    assert!(lazy.peek().is_none());
+    assert_eq!(format!("{:?}", lazy), "Tracked(\"<uninitialized>\")");
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let lazy = lazy.clone();
+            std::thread::spawn(move || lazy.borrow().clone())
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), "cfg:loaded");
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    lazy.try_replace(String::from("cfg:reloaded")).unwrap();
+    assert_eq!(*lazy.borrow(), "cfg:reloaded");
+}
+
+#[test]
+fn test_lazy_boxed_replace_status_before_init() {
+    let lazy: AnyCow<String> = AnyCow::lazy_boxed(|| unreachable!("replaced first"));
+    assert_eq!(
+        lazy.replace_status(String::from("a")),
+        Ok(WasInitialized::No)
+    );
+    assert_eq!(
+        lazy.replace_status(String::from("b")),
+        Ok(WasInitialized::Yes)
+    );
+    assert_eq!(*lazy.borrow(), "b");
+}
+
+#[test]
+fn test_into_updatable_reuses_lazy_arc() {
+    let lazy = AnyCow::lazy(|| vec![1, 2, 3]);
+    let snapshot = lazy.to_arc();
+
+    let updatable = lazy.into_updatable();
+    assert!(updatable.is_updatable());
+    assert!(Arc::ptr_eq(&snapshot, &updatable.to_arc()));
+
+    let uninitialized = AnyCow::lazy(|| String::from("fresh")).into_updatable();
+    assert_eq!(*uninitialized.borrow(), "fresh");
+}
+
+#[test]
+fn test_is_initialized() {
+    let lazy = AnyCow::lazy(|| 7);
+    assert!(!lazy.is_initialized());
+
+    let other = AnyCow::lazy(|| 8);
+    assert!(lazy < other);
+    assert!(lazy.is_initialized());
+    assert!(other.is_initialized());
+
+    assert!(AnyCow::updatable(1).is_initialized());
+}
+
+#[test]
+fn test_replace_status() {
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+    let lazy = AnyCow::lazy(|| {
+        RUNS.fetch_add(1, Ordering::SeqCst);
+        0
+    });
+
+    assert_eq!(lazy.replace_status(1), Ok(WasInitialized::No));
+    assert!(lazy.is_initialized());
+    assert_eq!(lazy.replace_status(2), Ok(WasInitialized::Yes));
+    assert_eq!(*lazy.borrow(), 2);
+    assert_eq!(RUNS.load(Ordering::SeqCst), 0);
+
+    assert_eq!(
+        AnyCow::updatable(1).replace_status(2),
+        Ok(WasInitialized::Yes)
+    );
+    assert!(AnyCow::owned(1).replace_status(2).is_err());
+}
+
+#[test]
+fn test_peek_never_initializes() {
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+    let lazy = AnyCow::lazy(|| {
+        RUNS.fetch_add(1, Ordering::SeqCst);
+        5
+    });
+
+    assert!(lazy.peek().is_none());
+    assert_eq!(RUNS.load(Ordering::SeqCst), 0);
+
+    let _ = lazy.borrow();
+    assert_eq!(lazy.peek().as_deref(), Some(&5));
+    assert_eq!(RUNS.load(Ordering::SeqCst), 1);
+
+    assert_eq!(AnyCow::owned(1).peek().as_deref(), Some(&1));
+    assert_eq!(AnyCow::updatable(2).peek().as_deref(), Some(&2));
+}
+
+#[test]
+fn test_from_once_lock_keeps_init_state() {
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+    fn init() -> i32 {
+        RUNS.fetch_add(1, Ordering::SeqCst);
+        0
+    }
+
+    let adopted = AnyCow::from_once_lock(OnceLock::from(7), init);
+    assert!(adopted.is_lazy() && adopted.is_initialized());
+    assert_eq!(*adopted.borrow(), 7);
+    adopted.try_replace(8).unwrap();
+    assert_eq!(*adopted.borrow(), 8);
+    assert_eq!(RUNS.load(Ordering::SeqCst), 0);
+
+    let empty = AnyCow::from_once_lock(OnceLock::new(), init);
+    assert!(!empty.is_initialized());
+    assert_eq!(*empty.borrow(), 0);
+    assert_eq!(RUNS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_self_comparison_reads_value() {
+    let nan = AnyCow::lazy(|| f64::NAN);
+    assert!(!nan.eq(&nan));
+    assert_eq!(nan.partial_cmp(&nan), None);
+    assert!(nan.is_initialized());
+}
@@ -0,0 +1,59 @@
+use anycow::LocalAnyCow;
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[test]
+fn test_local_variants() {
+    let value = String::from("hello");
+    let borrowed = LocalAnyCow::borrowed(&value);
+    let owned = LocalAnyCow::owned(String::from("hello"));
+    let shared = LocalAnyCow::shared(Rc::new(String::from("hello")));
+
+    assert!(borrowed.is_borrowed());
+    assert!(owned.is_owned());
+    assert!(shared.is_shared());
+    assert_eq!(borrowed, owned);
+    assert_eq!(owned, shared);
+}
+
+#[test]
+fn test_local_clone_shares_rc() {
+    let rc = Rc::new(vec![1, 2, 3]);
+    let cow = LocalAnyCow::from(rc.clone());
+    let copy = cow.clone();
+    assert_eq!(Rc::strong_count(&rc), 3);
+    assert!(Rc::ptr_eq(&copy.to_rc(), &rc));
+}
+
+#[test]
+fn test_local_into_owned_unwraps_unique_rc() {
+    let cow = LocalAnyCow::shared(Rc::new(vec![1, 2, 3]));
+    assert_eq!(cow.into_owned(), vec![1, 2, 3]);
+
+    let rc = Rc::new(vec![4]);
+    let cow = LocalAnyCow::shared(rc.clone());
+    assert_eq!(cow.into_owned(), vec![4]);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn test_local_to_mut() {
+    let rc = Rc::new(String::from("a"));
+    let mut cow = LocalAnyCow::shared(rc.clone());
+    cow.to_mut().push('b');
+    assert!(cow.is_owned());
+    assert_eq!(*cow, "ab");
+    assert_eq!(*rc, "a");
+}
+
+#[test]
+fn test_local_over_arc() {
+    let arc = Arc::new(vec![1, 2]);
+    let mut cow: LocalAnyCow<'_, Vec<i32>, Arc<Vec<i32>>> = LocalAnyCow::from(arc.clone());
+    assert!(Arc::ptr_eq(&cow.to_shared(), &arc));
+
+    cow.to_mut().push(3);
+    assert!(cow.is_owned());
+    assert_eq!(*arc, vec![1, 2]);
+    assert_eq!(cow.into_owned(), vec![1, 2, 3]);
+}
@@ -0,0 +1,22 @@
+#![cfg(feature = "rayon")]
+
+use anycow::AnyCow;
+
+#[test]
+fn test_par_map_preserves_order() {
+    let data: Vec<u32> = (0..100_000).collect();
+    let mapped = AnyCow::borrowed(&data).par_map(|x| x.to_string());
+    assert!(mapped.is_owned());
+    assert_eq!(mapped.borrow().len(), data.len());
+    assert_eq!(mapped.borrow()[12345], "12345");
+}
+
+#[test]
+fn test_par_map_lazy_and_updatable() {
+    let lazy = AnyCow::lazy(|| vec![1, 2, 3]);
+    assert_eq!(*lazy.par_map(|x| x * 10).borrow(), vec![10, 20, 30]);
+
+    let updatable = AnyCow::updatable(vec![String::from("a")]);
+    updatable.try_replace(vec![String::from("b")]).unwrap();
+    assert_eq!(*updatable.par_map(|s| s.len()).borrow(), vec![1]);
+}
@@ -0,0 +1,114 @@
+#![cfg(feature = "serde")]
+
+use anycow::{AnyCow, AsUpdatable};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Serialize, Deserialize)]
+struct Config {
+    name: AnyCow<'static, String>,
+    #[serde(with = "anycow::as_updatable")]
+    level: AnyCow<'static, u8>,
+    #[serde(with = "anycow::as_shared")]
+    hosts: AnyCow<'static, Vec<String>>,
+}
+
+#[test]
+fn test_fields_deserialize_into_chosen_variants() {
+    let json = r#"{"name":"api","level":3,"hosts":["a","b"]}"#;
+    let config: Config = serde_json::from_str(json).unwrap();
+
+    assert!(config.name.is_owned());
+    assert!(config.level.is_updatable());
+    assert!(config.hosts.is_shared());
+    assert_eq!(*config.hosts.borrow(), vec!["a", "b"]);
+
+    config.level.try_replace(4).unwrap();
+    assert_eq!(
+        serde_json::to_string(&config).unwrap(),
+        r#"{"name":"api","level":4,"hosts":["a","b"]}"#
+    );
+}
+
+#[test]
+fn test_serialize_every_variant() {
+    let value = 7;
+    let cows = [
+        AnyCow::borrowed(&value),
+        AnyCow::shared(Arc::new(7)),
+        AnyCow::updatable(7),
+        AnyCow::lazy(|| 7),
+    ];
+    for cow in &cows {
+        assert_eq!(serde_json::to_string(cow).unwrap(), "7");
+    }
+}
+
+#[test]
+fn test_as_updatable_newtype_in_collections() {
+    let values: Vec<AsUpdatable<i32>> = serde_json::from_str("[1, 2]").unwrap();
+    assert!(values.iter().all(|value| value.is_updatable()));
+    values[0].try_replace(10).unwrap();
+    assert_eq!(serde_json::to_string(&values).unwrap(), "[10,2]");
+}
+
+#[derive(Serialize, Deserialize)]
+struct Blobs {
+    #[serde(with = "anycow::as_base64")]
+    base64: AnyCow<'static, Vec<u8>>,
+    #[serde(with = "anycow::as_hex")]
+    hex: AnyCow<'static, Vec<u8>>,
+}
+
+fn round_trip(bytes: Vec<u8>) {
+    let blobs = Blobs {
+        base64: AnyCow::updatable(bytes.clone()),
+        hex: AnyCow::shared(Arc::new(bytes.clone())),
+    };
+    let json = serde_json::to_string(&blobs).unwrap();
+    let decoded: Blobs = serde_json::from_str(&json).unwrap();
+    assert!(decoded.base64.is_owned() && decoded.hex.is_owned());
+    assert_eq!(*decoded.base64.borrow(), bytes);
+    assert_eq!(*decoded.hex.borrow(), bytes);
+}
+
+#[test]
+fn test_byte_encodings_round_trip() {
+    round_trip(Vec::new());
+    for len in 1..=5 {
+        round_trip((0..len).collect());
+    }
+    round_trip((0..1_000_000u32).map(|i| (i * 31 % 251) as u8).collect());
+}
+
+#[test]
+fn test_byte_encodings_match_rfc_4648_vectors() {
+    let cases = [
+        ("", "", ""),
+        ("f", "Zg==", "66"),
+        ("fo", "Zm8=", "666f"),
+        ("foo", "Zm9v", "666f6f"),
+        ("foobar", "Zm9vYmFy", "666f6f626172"),
+    ];
+    for (raw, base64, hex) in cases {
+        let blobs = Blobs {
+            base64: AnyCow::owned(raw.as_bytes().to_vec()),
+            hex: AnyCow::owned(raw.as_bytes().to_vec()),
+        };
+        let expected = format!(r#"{{"base64":"{base64}","hex":"{hex}"}}"#);
+        assert_eq!(serde_json::to_string(&blobs).unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_byte_encodings_reject_malformed_input() {
+    for json in [
+        r#"{"base64":"Zm9","hex":""}"#,
+        r#"{"base64":"Zm=v","hex":""}"#,
+        r#"{"base64":"Zm9v!A==","hex":""}"#,
+        r#"{"base64":"","hex":"abc"}"#,
+        r#"{"base64":"","hex":"zz"}"#,
+    ] {
+        assert!(serde_json::from_str::<Blobs>(json).is_err(), "{json}");
+    }
+}
@@ -0,0 +1,65 @@
+use anycow::SharedAnyCow;
+use std::sync::Arc;
+
+trait Backend: Send + Sync {
+    fn name(&self) -> &'static str;
+}
+
+struct Stdout;
+impl Backend for Stdout {
+    fn name(&self) -> &'static str {
+        "stdout"
+    }
+}
+
+struct File;
+impl Backend for File {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+}
+
+#[test]
+fn test_swap_trait_object() {
+    let backend: SharedAnyCow<dyn Backend> = SharedAnyCow::updatable(Arc::new(Stdout));
+    let reader = backend.borrow();
+    assert!(backend.try_replace(Arc::new(File)).is_ok());
+
+    // The guard keeps the old implementation alive
+    assert_eq!(reader.name(), "stdout");
+    assert_eq!(backend.borrow().name(), "file");
+    assert_eq!(backend.to_arc().name(), "file");
+}
+
+#[test]
+fn test_shared_is_not_replaceable() {
+    let backend: SharedAnyCow<dyn Backend> =
+        SharedAnyCow::from(Arc::new(Stdout) as Arc<dyn Backend>);
+    assert!(backend.is_shared());
+    assert!(backend.try_replace(Arc::new(File)).is_err());
+    assert_eq!(backend.borrow().name(), "stdout");
+}
+
+#[test]
+fn test_unsized_slice() {
+    let data: SharedAnyCow<[u8]> = SharedAnyCow::updatable(Arc::from(&b"abc"[..]));
+    let copy = data.clone();
+    data.try_replace(Arc::from(&b"xyz"[..])).unwrap();
+    assert_eq!(&*data.borrow(), b"xyz");
+    assert_eq!(&*copy.borrow(), b"abc");
+    assert_eq!(
+        format!("{:?}", SharedAnyCow::<str>::shared(Arc::from("hi"))),
+        "Shared(\"hi\")"
+    );
+}
+
+#[test]
+fn test_collect_into_shared_slice() {
+    let names: SharedAnyCow<[String]> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+    assert!(names.is_shared());
+    assert_eq!(names.borrow().len(), 3);
+
+    let evens = SharedAnyCow::shared_from_iter((0..10).filter(|n| n % 2 == 0));
+    assert_eq!(&*evens.borrow(), &[0, 2, 4, 6, 8]);
+    assert_eq!(Arc::strong_count(&evens.to_arc()), 2);
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "tokio")]
+
+use anycow::AnyCow;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_changed_wakes_on_replace_from_another_task() {
+    let config = Arc::new(AnyCow::updatable_tracked(1));
+    let changed = config.changed().unwrap();
+
+    let writer = config.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        writer.try_replace(2).unwrap();
+    });
+
+    assert_eq!(*changed.await, 2);
+}
+
+#[tokio::test]
+async fn test_changed_is_cancel_safe() {
+    let config = AnyCow::updatable_tracked(0);
+
+    let timed_out =
+        tokio::time::timeout(Duration::from_millis(10), config.changed().unwrap()).await;
+    assert!(timed_out.is_err());
+
+    let changed = config.changed().unwrap();
+    config.try_replace(5).unwrap();
+    assert_eq!(*changed.await, 5);
+}
+
+#[tokio::test]
+async fn test_changed_on_plain_updatable() {
+    let config = Arc::new(AnyCow::updatable(1));
+    let changed = config.changed().unwrap();
+
+    let writer = config.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        writer.try_replace(2).unwrap();
+    });
+
+    assert_eq!(*changed.await, 2);
+
+    // A store before the first poll counts
+    let changed = config.changed().unwrap();
+    config.try_replace(3).unwrap();
+    assert_eq!(*changed.await, 3);
+}
+
+#[test]
+fn test_changed_requires_updatable_variant() {
+    assert!(AnyCow::owned(1).changed().is_none());
+    assert!(AnyCow::shared(Arc::new(1)).changed().is_none());
+}
@@ -0,0 +1,88 @@
+use anycow::{InitPolicy, TryLazy};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+#[test]
+fn test_cached_error_is_not_retried() {
+    let calls = AtomicUsize::new(0);
+    let lazy = TryLazy::cached(|| {
+        calls.fetch_add(1, Ordering::SeqCst);
+        "x".parse::<i32>().map_err(|e| e.to_string())
+    });
+    assert_eq!(lazy.policy(), InitPolicy::Cache);
+
+    let first = lazy.get().err().unwrap();
+    assert_eq!(lazy.get().err(), Some(first.clone()));
+    assert_eq!(lazy.error(), Some(&first));
+    assert!(!lazy.is_initialized());
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_retry_until_success() {
+    let calls = AtomicUsize::new(0);
+    let lazy = TryLazy::retry(|| {
+        let n = calls.fetch_add(1, Ordering::SeqCst);
+        if n < 2 {
+            Err(n)
+        } else {
+            Ok(vec![n])
+        }
+    });
+
+    assert_eq!(lazy.get().err(), Some(0));
+    assert_eq!(lazy.get().err(), Some(1));
+    assert_eq!(lazy.error(), None);
+    assert_eq!(*lazy.get().unwrap(), vec![2]);
+    assert_eq!(*lazy.to_arc().unwrap(), vec![2]);
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_try_replace_requires_initialization() {
+    let lazy = TryLazy::retry(|| Ok::<_, ()>(1));
+    assert!(lazy.try_replace(2).is_err());
+    assert_eq!(*lazy.get().unwrap(), 1);
+    lazy.try_replace(2).unwrap();
+    assert_eq!(*lazy.get().unwrap(), 2);
+}
+
+#[test]
+fn test_panicking_initializer_does_not_poison() {
+    let calls = AtomicUsize::new(0);
+    let lazy = TryLazy::cached(|| {
+        if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+            panic!("boom");
+        }
+        Ok::<_, ()>(5)
+    });
+
+    thread::scope(|s| {
+        assert!(s.spawn(|| lazy.get().map(|v| *v)).join().is_err());
+    });
+    assert_eq!(*lazy.get().unwrap(), 5);
+}
+
+#[test]
+fn test_concurrent_init_runs_once() {
+    let calls = AtomicUsize::new(0);
+    let lazy = TryLazy::retry(|| {
+        calls.fetch_add(1, Ordering::SeqCst);
+        Ok::<_, ()>(String::from("ready"))
+    });
+
+    thread::scope(|s| {
+        for _ in 0..8 {
+            s.spawn(|| assert_eq!(*lazy.get().unwrap(), "ready"));
+        }
+    });
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+static STATIC_VALUE: TryLazy<u32, ()> = TryLazy::retry(|| Ok(7));
+
+#[test]
+fn test_static_try_lazy() {
+    assert_eq!(*STATIC_VALUE.get().unwrap(), 7);
+    assert_eq!(format!("{:?}", STATIC_VALUE), "TryLazy(Ok(7))");
+}
@@ -0,0 +1,846 @@
+use anycow::{AnyCow, CasError, UpdatableCell};
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn test_history_ring_drops_oldest() {
+    let cow = AnyCow::updatable_with_history(0, 3);
+    for i in 1..=5 {
+        cow.try_replace(i).unwrap();
+    }
+
+    let history: Vec<i32> = cow.history().iter().map(|v| **v).collect();
+    assert_eq!(history, vec![2, 3, 4]);
+    assert_eq!(*cow.borrow(), 5);
+}
+
+#[test]
+fn test_history_reuses_snapshots() {
+    let cow = AnyCow::updatable_with_history(vec![1, 2, 3], 1);
+    let before = cow.to_arc();
+    cow.try_replace(vec![4]).unwrap();
+
+    assert!(Arc::ptr_eq(&before, &cow.history()[0]));
+}
+
+#[test]
+fn test_history_zero_capacity() {
+    let cow = AnyCow::updatable_with_history(1, 0);
+    cow.try_replace(2).unwrap();
+    assert!(cow.history().is_empty());
+}
+
+#[test]
+fn test_fold_history_oldest_first() {
+    let cow = AnyCow::updatable_with_history(0, 3);
+    for i in 1..=4 {
+        cow.try_replace(i).unwrap();
+    }
+    let order = cow.fold_history(Vec::new(), |mut seen, value| {
+        seen.push(*value);
+        seen
+    });
+    assert_eq!(order, vec![1, 2, 3]);
+
+    let plain = AnyCow::updatable(0);
+    plain.try_replace(1).unwrap();
+    assert_eq!(plain.fold_history(7, |acc, value| acc + value), 7);
+}
+
+#[test]
+fn test_clone_does_not_copy_history() {
+    let cow = AnyCow::updatable_with_history(1, 4);
+    cow.try_replace(2).unwrap();
+
+    let cloned = cow.clone();
+    cloned.try_replace(3).unwrap();
+    assert!(cloned.history().is_empty());
+    assert_eq!(cow.history().len(), 1);
+}
+
+#[test]
+fn test_mapped_ref_outlives_replacement() {
+    let cow = AnyCow::updatable(String::from("before"));
+    let old = cow.as_str();
+
+    cow.try_replace(String::from("after")).unwrap();
+
+    // The mapped reference still points at the snapshot it was taken from
+    assert_eq!(&*old, "before");
+    assert_eq!(&*cow.as_str(), "after");
+}
+
+#[test]
+fn test_extend_variants() {
+    let mut owned = AnyCow::owned(vec![1]);
+    owned.extend([2, 3]);
+    assert!(owned.is_owned());
+    assert_eq!(*owned.borrow(), vec![1, 2, 3]);
+
+    let data = vec![1];
+    let mut borrowed = AnyCow::borrowed(&data);
+    borrowed.extend([2]);
+    assert!(borrowed.is_owned());
+    assert_eq!(data, vec![1]);
+
+    let mut lazy = AnyCow::lazy(|| String::from("ab"));
+    lazy.extend(['c', 'd']);
+    assert!(lazy.is_lazy());
+    assert_eq!(*lazy.borrow(), "abcd");
+}
+
+#[test]
+fn test_extend_updatable_keeps_old_snapshot() {
+    let mut cow = AnyCow::updatable_with_history(vec![1], 1);
+    let snapshot = cow.to_arc();
+    cow.extend([2]);
+
+    assert_eq!(*snapshot, vec![1]);
+    assert_eq!(*cow.borrow(), vec![1, 2]);
+    assert!(Arc::ptr_eq(&snapshot, &cow.history()[0]));
+}
+
+#[test]
+fn test_compact_variants() {
+    let mut items = Vec::with_capacity(64);
+    items.push(1);
+
+    let lazy = AnyCow::lazy(|| {
+        let mut s = String::with_capacity(64);
+        s.push('x');
+        s
+    });
+    lazy.compact().unwrap();
+    assert_eq!(lazy.borrow().capacity(), 1);
+
+    let owned = AnyCow::owned(items);
+    assert!(owned.compact().is_err());
+}
+
+#[test]
+fn test_compact_records_history() {
+    let cow = AnyCow::updatable_with_history(Vec::<u8>::with_capacity(8), 2);
+    cow.compact().unwrap();
+    assert_eq!(cow.history().len(), 1);
+}
+
+#[test]
+fn test_replace_if_concurrent_increments() {
+    // A non-PartialEq payload, so value-based compare-and-swap isn't an option
+    struct Counter(u64);
+    impl Clone for Counter {
+        fn clone(&self) -> Self {
+            Counter(self.0)
+        }
+    }
+
+    let cow = Arc::new(AnyCow::updatable(Counter(0)));
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let cow = cow.clone();
+            std::thread::spawn(move || {
+                for _ in 0..100 {
+                    loop {
+                        let token = cow.snapshot_token().unwrap();
+                        let next = Counter(token.value().0 + 1);
+                        if cow.replace_if(token, next).is_ok() {
+                            break;
+                        }
+                    }
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(cow.borrow().0, 400);
+}
+
+#[test]
+fn test_replace_if_non_updatable() {
+    let owned = AnyCow::owned(1);
+    assert!(owned.snapshot_token().is_none());
+
+    let token = AnyCow::updatable(1).snapshot_token().unwrap();
+    assert_eq!(owned.replace_if(token, 2), Err(2));
+}
+
+#[test]
+fn test_retain_collections() {
+    let vec = AnyCow::updatable(vec![1, 2, 3, 4]);
+    vec.retain(|x| x % 2 == 0).unwrap();
+    assert_eq!(*vec.borrow(), vec![2, 4]);
+
+    let text = AnyCow::lazy(|| String::from("a-b-c"));
+    text.retain(|c| c != '-').unwrap();
+    assert_eq!(*text.borrow(), "abc");
+
+    let set = AnyCow::updatable(std::collections::BTreeSet::from([1, 5, 10]));
+    set.retain(|x| *x >= 5).unwrap();
+    assert_eq!(set.borrow().len(), 2);
+
+    let owned = AnyCow::owned(vec![1]);
+    assert!(owned.retain(|_| false).is_err());
+}
+
+#[test]
+fn test_edit_commit() {
+    let cow = AnyCow::updatable(vec![1]);
+    let mut editor = cow.edit().unwrap();
+    editor.push(2);
+    editor.push(3);
+    assert_eq!(*cow.borrow(), vec![1]);
+    assert_eq!(*editor.original(), vec![1]);
+    editor.commit().unwrap();
+    assert_eq!(*cow.borrow(), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_edit_conflict() {
+    let cow = AnyCow::updatable(vec![1]);
+    let mut editor = cow.edit().unwrap();
+    editor.push(2);
+    cow.try_replace(vec![10]).unwrap();
+    assert_eq!(editor.commit(), Err(vec![1, 2]));
+    assert_eq!(*cow.borrow(), vec![10]);
+
+    let dropped = cow.edit().unwrap();
+    drop(dropped);
+    assert_eq!(*cow.borrow(), vec![10]);
+}
+
+#[test]
+fn test_edit_non_updatable() {
+    let value = vec![1];
+    assert!(AnyCow::borrowed(&value).edit().is_none());
+    assert!(AnyCow::owned(vec![1]).edit().is_none());
+}
+
+#[test]
+fn test_watch_coalesces_updates() {
+    let cow = AnyCow::updatable_tracked(0);
+    let mut watcher = cow.watch().unwrap();
+    assert!(!watcher.has_changed());
+    assert_eq!(watcher.next_timeout(Duration::from_millis(10)), None);
+
+    cow.try_replace(1).unwrap();
+    cow.try_replace(2).unwrap();
+    assert!(watcher.has_changed());
+    assert_eq!(*watcher.next().unwrap(), 2);
+    assert!(!watcher.has_changed());
+}
+
+#[test]
+fn test_watch_across_threads() {
+    let cow = AnyCow::updatable_tracked(0);
+    let mut seen = Vec::new();
+    thread::scope(|s| {
+        let watcher = cow.watch().unwrap();
+        s.spawn(|| {
+            for i in 1..=3 {
+                cow.try_replace(i).unwrap();
+                thread::sleep(Duration::from_millis(5));
+            }
+        });
+        for snapshot in watcher {
+            seen.push(*snapshot);
+            if *snapshot == 3 {
+                break;
+            }
+        }
+    });
+    assert_eq!(seen.last(), Some(&3));
+    assert!(seen.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[test]
+fn test_watch_non_updatable() {
+    assert!(AnyCow::owned(1).watch().is_none());
+    assert!(AnyCow::shared(Arc::new(1)).watch().is_none());
+}
+
+#[test]
+fn test_watch_plain_updatable_and_lazy() {
+    let cow = AnyCow::updatable(0);
+    let mut watcher = cow.watch().unwrap();
+    assert!(!watcher.has_changed());
+    assert_eq!(watcher.next_timeout(Duration::from_millis(10)), None);
+
+    cow.try_replace(1).unwrap();
+    cow.try_replace(2).unwrap();
+    assert!(watcher.has_changed());
+    assert_eq!(*watcher.next().unwrap(), 2);
+    assert!(!watcher.has_changed());
+
+    // Storing an equal value is still a replacement
+    cow.try_replace(2).unwrap();
+    assert_eq!(*watcher.next().unwrap(), 2);
+
+    let lazy = AnyCow::lazy(|| 0);
+    thread::scope(|s| {
+        let mut watcher = lazy.watch().unwrap();
+        assert!(lazy.is_initialized());
+        s.spawn(|| {
+            thread::sleep(Duration::from_millis(5));
+            lazy.try_replace(7).unwrap();
+        });
+        assert_eq!(*watcher.next().unwrap(), 7);
+    });
+}
+
+#[test]
+fn test_updatable_padded() {
+    let cow = AnyCow::updatable_padded(vec![1]);
+    let mut watcher = cow.watch().unwrap();
+    cow.try_replace(vec![2]).unwrap();
+    assert_eq!(*watcher.next().unwrap(), vec![2]);
+    assert_eq!(*cow.clone().borrow(), vec![2]);
+    assert_eq!(cow.into_owned(), vec![2]);
+}
+
+#[test]
+fn test_option_helpers() {
+    let cow = AnyCow::updatable(None);
+    cow.set_some(1).unwrap();
+    assert_eq!(*cow.borrow(), Some(1));
+
+    let reader = cow.borrow();
+    assert_eq!(cow.take_option().as_deref(), Some(&1));
+    assert_eq!(*reader, Some(1));
+    assert_eq!(*cow.borrow(), None);
+
+    cow.set_some(2).unwrap();
+    cow.set_none().unwrap();
+    assert!(cow.take_option().is_none());
+
+    let owned = AnyCow::owned(Some(3));
+    assert!(owned.set_some(4).is_err());
+    assert!(owned.take_option().is_none());
+    assert_eq!(*owned.borrow(), Some(3));
+}
+
+#[test]
+fn test_updatable_handle_concurrent_rcu() {
+    let cow = AnyCow::updatable_with_history(0u64, 1000);
+    let handle = cow.as_updatable().unwrap();
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(move || {
+                for _ in 0..100 {
+                    handle.rcu(|n| n + 1).unwrap();
+                }
+            });
+        }
+    });
+    assert_eq!(*cow.borrow(), 400);
+    assert_eq!(cow.history().len(), 400);
+}
+
+#[test]
+fn test_updatable_handle_initializes_lazy() {
+    let lazy = AnyCow::lazy(|| String::from("init"));
+    let handle = lazy.as_updatable().unwrap();
+    assert_eq!(*handle.load_full(), "init");
+    handle.store(String::from("stored")).unwrap();
+    assert_eq!(*lazy.borrow(), "stored");
+}
+
+#[test]
+fn test_replace_if_changed_skips_watchers() {
+    let cow = AnyCow::updatable_tracked(vec![1]);
+    let watcher = cow.watch().unwrap();
+    assert_eq!(cow.replace_if_changed(vec![1]), Ok(false));
+    assert!(!watcher.has_changed());
+    assert_eq!(cow.replace_if_changed(vec![2]), Ok(true));
+    assert!(watcher.has_changed());
+    assert_eq!(AnyCow::owned(1).replace_if_changed(2), Err(2));
+}
+
+#[test]
+fn test_from_arc_swap_shares_cell() {
+    use arc_swap::ArcSwap;
+
+    let cell = Arc::new(ArcSwap::from_pointee(vec![1]));
+    let a = AnyCow::from_arc_swap(cell.clone());
+    let b = AnyCow::from_arc_swap(cell.clone());
+    assert!(a.is_updatable());
+
+    b.try_replace(vec![2]).unwrap();
+    assert_eq!(*a.borrow(), vec![2]);
+    assert_eq!(**cell.load(), vec![2]);
+
+    // Consuming one view leaves the shared cell intact
+    assert_eq!(a.into_owned(), vec![2]);
+    assert_eq!(*b.into_inner_arc().unwrap(), vec![2]);
+    assert_eq!(**cell.load(), vec![2]);
+}
+
+#[test]
+fn test_scoped_replace_restores_on_panic() {
+    let cow = AnyCow::updatable(String::from("prod"));
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let guard = cow.scoped_replace(String::from("test")).unwrap();
+        assert_eq!(guard.previous(), "prod");
+        assert_eq!(*cow.borrow(), "test");
+        panic!("test failure");
+    }));
+    assert!(result.is_err());
+    assert_eq!(*cow.borrow(), "prod");
+}
+
+#[test]
+fn test_scoped_replace_commit_and_non_updatable() {
+    let cow = AnyCow::updatable(1);
+    cow.scoped_replace(2).unwrap().commit();
+    assert_eq!(*cow.borrow(), 2);
+    assert!(AnyCow::owned(1).scoped_replace(2).is_none());
+}
+
+#[test]
+fn test_updatable_with_refresh_serves_cached_value_within_ttl() {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    let fetches = Arc::new(AtomicU32::new(0));
+    let counter = fetches.clone();
+    let cow = AnyCow::updatable_with_refresh(0, Duration::from_millis(50), move || {
+        counter.fetch_add(1, Ordering::SeqCst) + 1
+    });
+
+    assert_eq!(*cow.borrow(), 0);
+    assert_eq!(*cow.borrow(), 0);
+    assert_eq!(fetches.load(Ordering::SeqCst), 0);
+
+    thread::sleep(Duration::from_millis(60));
+    assert_eq!(*cow.borrow(), 1);
+    assert_eq!(*cow.borrow(), 1);
+    assert_eq!(fetches.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_updatable_with_refresh_single_refresher() {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    let running = Arc::new(AtomicU32::new(0));
+    let overlapped = Arc::new(AtomicU32::new(0));
+    let (running_in, overlapped_in) = (running.clone(), overlapped.clone());
+    let cow = AnyCow::updatable_with_refresh(0u32, Duration::ZERO, move || {
+        if running_in.fetch_add(1, Ordering::SeqCst) > 0 {
+            overlapped_in.fetch_add(1, Ordering::SeqCst);
+        }
+        thread::sleep(Duration::from_millis(1));
+        running_in.fetch_sub(1, Ordering::SeqCst);
+        1
+    });
+
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for _ in 0..20 {
+                    let _ = *cow.borrow();
+                }
+            });
+        }
+    });
+    assert_eq!(overlapped.load(Ordering::SeqCst), 0);
+    assert_eq!(*cow.borrow(), 1);
+}
+
+#[test]
+fn test_ref_count_tracks_snapshots() {
+    let cow = AnyCow::updatable(String::from("value"));
+    assert_eq!(cow.ref_count(), Some(1));
+
+    let snapshot = cow.to_arc();
+    assert_eq!(cow.ref_count(), Some(2));
+    drop(snapshot);
+    assert_eq!(cow.ref_count(), Some(1));
+
+    let lazy = AnyCow::lazy(|| 1);
+    assert_eq!(lazy.ref_count(), None);
+    assert!(!lazy.is_initialized());
+    let _ = lazy.borrow();
+    assert_eq!(lazy.ref_count(), Some(1));
+}
+
+#[test]
+fn test_replace_all_skips_fixed_variants() {
+    let cows = vec![
+        AnyCow::lazy(|| 0),
+        AnyCow::shared(Arc::new(0)),
+        AnyCow::updatable(0),
+    ];
+    assert_eq!(anycow::replace_all(&cows, 5), 2);
+    assert_eq!(*cows[0].borrow(), 5);
+    assert_eq!(*cows[1].borrow(), 0);
+    assert_eq!(*cows[2].borrow(), 5);
+
+    let snapshot = Arc::new(9);
+    assert_eq!(anycow::replace_all_arc(&cows, snapshot.clone()), 2);
+    assert_eq!(Arc::strong_count(&snapshot), 3);
+}
+
+#[test]
+fn test_try_replace_tracked_counts_generations() {
+    let cow = AnyCow::updatable_tracked(0);
+    let first = cow.try_replace_tracked(1).unwrap();
+    let second = cow.try_replace_tracked(2).unwrap();
+    assert_eq!(second.observed_generation, first.generation);
+    assert_eq!(second.concurrent_writes(), 0);
+
+    assert!(AnyCow::owned(0).try_replace_tracked(1).is_err());
+    assert!(AnyCow::lazy(|| 0).try_replace_tracked(1).is_err());
+}
+
+#[test]
+fn test_fetch_max_and_min_under_contention() {
+    let high = AnyCow::updatable(0u32);
+    let low = AnyCow::lazy(|| u32::MAX);
+    thread::scope(|s| {
+        for t in 0..4u32 {
+            let (high, low) = (&high, &low);
+            s.spawn(move || {
+                for i in 0..250 {
+                    let sample = i * 4 + t;
+                    high.fetch_max(sample).unwrap();
+                    low.fetch_min(sample + 1).unwrap();
+                }
+            });
+        }
+    });
+    assert_eq!(*high.borrow(), 999);
+    assert_eq!(*low.borrow(), 1);
+    assert!(AnyCow::owned(1).fetch_max(2).is_err());
+}
+
+#[test]
+fn test_map_guard_keeps_replaced_value_alive() {
+    let cow = AnyCow::updatable((1, vec![String::from("first")]));
+    let names = cow.map_guard(|pair| &pair.1);
+    cow.try_replace((2, Vec::new())).unwrap();
+    assert_eq!(names.len(), 1);
+    assert_eq!(*cow.map_guard(|pair| &pair.0), 2);
+}
+
+#[test]
+fn test_validated_rejects_invalid_replacements() {
+    let cow = AnyCow::updatable_validated(vec![1], |v: &Vec<i32>| {
+        if v.is_empty() {
+            Err("must not be empty")
+        } else {
+            Ok(())
+        }
+    });
+
+    assert!(cow.try_replace(Vec::new()).is_err());
+    assert!(cow.try_replace_arc(Arc::new(Vec::new())).is_err());
+    assert_eq!(*cow.borrow(), vec![1]);
+
+    cow.try_replace_validated(vec![2, 3]).unwrap();
+    assert_eq!(*cow.borrow(), vec![2, 3]);
+
+    let rejected = AnyCow::owned(1).try_replace_validated(2).unwrap_err();
+    assert!(rejected.reason().is_none());
+    assert_eq!(rejected.into_value(), 2);
+}
+
+#[test]
+fn test_validated_guards_every_write_path() {
+    let cow = AnyCow::updatable_validated(vec![3, 1, 2], |v: &Vec<i32>| {
+        if v.is_empty() {
+            Err("must not be empty")
+        } else {
+            Ok(())
+        }
+    });
+
+    assert_eq!(cow.replace_if_changed(Vec::new()), Err(Vec::new()));
+    assert!(cow.clear().is_err());
+    assert!(cow.retain(|_| false).is_err());
+    assert!(cow.try_replace_tracked(Vec::new()).is_err());
+    assert!(cow.scoped_replace(Vec::new()).is_none());
+
+    let mut editor = cow.edit().unwrap();
+    editor.clear();
+    assert_eq!(editor.commit(), Err(Vec::new()));
+
+    let handle = cow.as_updatable().unwrap();
+    assert!(handle.store(Vec::new()).is_err());
+    assert!(handle.rcu(|_| Vec::new()).is_err());
+    let current = handle.load_full();
+    assert!(matches!(
+        handle.compare_and_swap(&current, Vec::new()),
+        Err(CasError::Rejected(_))
+    ));
+    let cell = handle.cell().unwrap();
+    assert!(cell.store(Arc::new(Vec::new())).is_err());
+
+    assert_eq!(*cow.borrow(), vec![3, 1, 2]);
+    assert!(cow.history().is_empty());
+}
+
+#[test]
+fn test_updatable_with_capacity() {
+    let cow: AnyCow<HashMap<String, u32>> = AnyCow::updatable_with_capacity(32);
+    assert!(cow.is_updatable());
+    assert!(cow.borrow().is_empty());
+    assert!(cow.borrow().capacity() >= 32);
+
+    let text: AnyCow<String> = AnyCow::updatable_with_capacity(128);
+    assert!(text.into_owned().capacity() >= 128);
+}
+
+#[test]
+fn test_retained_count_spots_guards_held_across_replace() {
+    let cow = AnyCow::updatable_tracked(String::from("v1"));
+    assert_eq!(cow.retained_count(), Some(0));
+
+    // Holding guards and snapshots across an update keeps the old value alive
+    let guard = cow.borrow();
+    let snapshot = cow.to_arc();
+    cow.try_replace(String::from("v2")).unwrap();
+    assert_eq!(cow.retained_count(), Some(2));
+    assert_eq!(*guard, "v1");
+    drop((guard, snapshot));
+
+    // Best practice: read what is needed, drop the guard, then replace
+    let next = format!("{}+", *cow.borrow());
+    cow.try_replace(next).unwrap();
+    assert_eq!(cow.retained_count(), Some(0));
+    cow.as_updatable()
+        .unwrap()
+        .rcu(|value| format!("{value}+"))
+        .unwrap();
+    assert_eq!(cow.retained_count(), Some(0));
+
+    let history = AnyCow::updatable_with_history(1, 4);
+    history.try_replace(2).unwrap();
+    assert_eq!(history.retained_count(), Some(0));
+
+    assert_eq!(AnyCow::owned(1).retained_count(), None);
+    assert_eq!(AnyCow::updatable(1).retained_count(), None);
+}
+
+#[test]
+fn test_try_replace_from() {
+    let staging = AnyCow::shared(Arc::new(vec![1, 2]));
+    let live = AnyCow::lazy(Vec::new);
+    live.try_replace_from(&staging).unwrap();
+    assert!(Arc::ptr_eq(&live.to_arc(), &staging.to_arc()));
+
+    let data = vec![3];
+    live.try_replace_from(&AnyCow::borrowed(&data)).unwrap();
+    assert_eq!(*live.borrow(), vec![3]);
+
+    assert!(AnyCow::owned(Vec::new()).try_replace_from(&live).is_err());
+}
+
+/// A hasher that replaces the hashed container after the first write.
+struct MeddlingHasher<'c> {
+    inner: std::collections::hash_map::DefaultHasher,
+    cow: &'c AnyCow<'static, Vec<u64>>,
+}
+
+impl Hasher for MeddlingHasher<'_> {
+    fn finish(&self) -> u64 {
+        self.inner.finish()
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.inner.write(bytes);
+        self.cow.try_replace(vec![9; 8]).unwrap();
+    }
+}
+
+#[test]
+fn test_hash_is_consistent_within_one_call() {
+    let mut expected = std::collections::hash_map::DefaultHasher::new();
+    vec![1u64, 2, 3].hash(&mut expected);
+    let expected = expected.finish();
+
+    let cow = AnyCow::updatable(vec![1u64, 2, 3]);
+    let mut state = MeddlingHasher {
+        inner: Default::default(),
+        cow: &cow,
+    };
+    cow.stable_hash(&mut state);
+    assert_eq!(state.finish(), expected);
+
+    cow.try_replace(vec![1, 2, 3]).unwrap();
+    let mut state = MeddlingHasher {
+        inner: Default::default(),
+        cow: &cow,
+    };
+    cow.hash(&mut state);
+    assert_eq!(state.finish(), expected);
+    assert_eq!(*cow.borrow(), vec![9; 8]);
+}
+
+#[test]
+fn test_extremum_by_key_outlives_replacement() {
+    let cow = AnyCow::updatable(vec![
+        String::from("bb"),
+        String::from("a"),
+        String::from("ccc"),
+    ]);
+    let longest = cow.max_by_key(String::len).unwrap();
+    let shortest = cow.min_by_key(String::len).unwrap();
+
+    cow.try_replace(Vec::new()).unwrap();
+    assert_eq!(*longest, "ccc");
+    assert_eq!(*shortest, "a");
+    assert!(cow.max_by_key(String::len).is_none());
+}
+
+#[test]
+fn test_set_and_clear_refresh() {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    let fetches = Arc::new(AtomicU32::new(0));
+    let counter = fetches.clone();
+    let cow = AnyCow::updatable_with_refresh(0, Duration::ZERO, move || {
+        counter.fetch_add(1, Ordering::SeqCst) + 1
+    });
+    assert_eq!(*cow.borrow(), 1);
+
+    assert!(cow.clear_refresh());
+    assert_eq!(*cow.borrow(), 1);
+    assert_eq!(*cow.borrow(), 1);
+
+    assert!(cow.set_refresh(Duration::ZERO));
+    assert_eq!(*cow.borrow(), 2);
+    assert_eq!(fetches.load(Ordering::SeqCst), 2);
+
+    assert!(!AnyCow::updatable(0).clear_refresh());
+    assert!(!AnyCow::lazy(|| 0).set_refresh(Duration::ZERO));
+}
+
+#[test]
+fn test_try_replace_computed_keeps_value_on_error() {
+    use anycow::ReplaceError;
+
+    let cow = AnyCow::updatable(vec![1]);
+    assert_eq!(
+        cow.try_replace_computed(|| Err::<Vec<i32>, _>("parse error")),
+        Err(ReplaceError::Compute("parse error"))
+    );
+    assert_eq!(*cow.borrow(), vec![1]);
+
+    cow.try_replace_computed(|| Ok::<_, ()>(vec![2])).unwrap();
+    assert_eq!(*cow.borrow(), vec![2]);
+
+    let mut ran = false;
+    let owned = AnyCow::owned(vec![1]);
+    let result = owned.try_replace_computed(|| {
+        ran = true;
+        Ok::<_, ()>(vec![2])
+    });
+    assert_eq!(result, Err(ReplaceError::NotReplaceable));
+    assert!(!ran);
+}
+
+#[test]
+fn test_try_replace_into_converts() {
+    let name = AnyCow::updatable(String::from("a"));
+    name.try_replace_into("b").unwrap();
+    assert_eq!(*name.borrow(), "b");
+
+    let lazy = AnyCow::<Box<str>>::lazy(|| "a".into());
+    lazy.try_replace_into(String::from("c")).unwrap();
+    assert_eq!(&**lazy.borrow(), "c");
+
+    let shared = AnyCow::shared(Arc::new(String::from("a")));
+    assert!(shared.try_replace_into("b").is_err());
+    assert_eq!(*shared.borrow(), "a");
+}
+
+#[test]
+fn test_debounced_drops_bursts() {
+    let cow = AnyCow::updatable_debounced(0, Duration::from_secs(3600));
+    for i in 1..=10 {
+        cow.try_replace(i).unwrap();
+    }
+    assert_eq!(*cow.borrow(), 1);
+
+    // Every write path is subject to the interval
+    cow.try_replace_arc(Arc::new(11)).unwrap();
+    cow.as_updatable().unwrap().store(12).unwrap();
+    cow.replace_if_changed(13).unwrap();
+    let stats = cow.try_replace_tracked(14).unwrap();
+    assert_eq!(stats.generation, stats.observed_generation);
+    assert_eq!(stats.concurrent_writes(), 0);
+    assert_eq!(*cow.borrow(), 1);
+}
+
+#[test]
+fn test_debounced_failed_compare_keeps_interval() {
+    let cow = AnyCow::updatable_debounced(0, Duration::from_secs(3600));
+    let handle = cow.as_updatable().unwrap();
+    let stale = Arc::new(0);
+    assert!(matches!(
+        handle.compare_and_swap(&stale, 1),
+        Err(CasError::Changed(_))
+    ));
+
+    let current = handle.load_full();
+    handle.compare_and_swap(&current, 1).unwrap();
+    assert_eq!(*cow.borrow(), 1);
+
+    // The interval has started now, and a stale compare still fails
+    assert!(matches!(
+        handle.compare_and_swap(&current, 2),
+        Err(CasError::Changed(_))
+    ));
+    handle.rcu(|n| n + 1).unwrap();
+    assert_eq!(*cow.borrow(), 1);
+}
+
+#[test]
+fn test_debounced_zero_interval_applies_everything() {
+    let cow = AnyCow::updatable_debounced(0, Duration::ZERO);
+    for i in 1..=3 {
+        cow.try_replace(i).unwrap();
+        assert_eq!(*cow.borrow(), i);
+    }
+    cow.as_updatable().unwrap().store(4).unwrap();
+    assert_eq!(*cow.borrow(), 4);
+}
+
+#[test]
+fn test_cell_builder_combines_policies() {
+    let cow: AnyCow<u32> = UpdatableCell::builder(1)
+        .history(4)
+        .validator(|&n: &u32| if n == 0 { Err("zero") } else { Ok(()) })
+        .debounce(Duration::ZERO)
+        .padded()
+        .build()
+        .into();
+    assert!(matches!(cow, AnyCow::Tracked(_)));
+
+    assert!(cow.try_replace(0).is_err());
+    cow.try_replace(2).unwrap();
+    cow.try_replace(3).unwrap();
+    let history: Vec<u32> = cow.history().iter().map(|v| **v).collect();
+    assert_eq!(history, vec![1, 2]);
+    assert_eq!(*cow.borrow(), 3);
+}
+
+#[test]
+fn test_plain_updatable_keeps_arc_swap_payload() {
+    let cow = AnyCow::updatable(1);
+    let AnyCow::Updatable(value) = &cow else {
+        panic!("updatable() creates an Updatable");
+    };
+    let value: &arc_swap::ArcSwap<i32> = value;
+    value.store(Arc::new(2));
+    assert_eq!(*cow.borrow(), 2);
+
+    let tracked = AnyCow::updatable_tracked(1);
+    assert!(tracked.is_updatable());
+    assert!(matches!(tracked, AnyCow::Tracked(_)));
+    assert!(matches!(tracked.clone(), AnyCow::Tracked(_)));
+}